@@ -0,0 +1,108 @@
+use aoc::{Error, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// Total calories carried by each elf, in input order.
+    type Input = Vec<u32>;
+    type Answer = u64;
+
+    fn parse(&self, data: &str) -> Result<Vec<u32>, Error> {
+        let mut elves = vec![];
+
+        for block in aoc::blocks(data) {
+            let mut current = 0u32;
+            for line in block {
+                let calories = line.number::<u32>(line.text.trim())?;
+                current = current
+                    .checked_add(calories)
+                    .ok_or_else(|| Error::Overflow {
+                        position: line.position(line.text),
+                        quantity: "the calories carried by this elf".to_string(),
+                    })?;
+            }
+            elves.push(current);
+        }
+
+        Ok(elves)
+    }
+
+    fn part_one(&self, elves: &Vec<u32>) -> Result<u64, Error> {
+        Ok(elves.iter().copied().max().unwrap_or(0) as u64)
+    }
+
+    fn part_two(&self, elves: &Vec<u32>) -> Result<u64, Error> {
+        let mut elves = elves.clone();
+        elves.sort_unstable_by(|a, b| b.cmp(a));
+        // three totals that each fit a u32 always fit a u64
+        Ok(elves.iter().take(3).map(|&calories| calories as u64).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DATA: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_part_one() {
+        let elves = Day1.parse(TEST_DATA).unwrap();
+        assert_eq!(Day1.part_one(&elves).unwrap(), 24000);
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let result = Day1.parse("1000\n\n2000\n3x00\n");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 4, col 1: expected number, got '3x00'"
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        let elves = Day1.parse(TEST_DATA).unwrap();
+        assert_eq!(Day1.part_two(&elves).unwrap(), 45000);
+    }
+
+    #[test]
+    fn test_too_many_calories() {
+        let elves = Day1
+            .parse(
+                "4294967295
+
+4294967295
+
+4294967295",
+            )
+            .unwrap();
+        assert_eq!(Day1.part_one(&elves).unwrap(), 4294967295);
+        assert_eq!(Day1.part_two(&elves).unwrap(), 3 * 4294967295);
+
+        let result = Day1.parse(
+            "1000
+
+4294967295
+1
+",
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 4, col 1: the calories carried by this elf is too large"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use aoc::{Error, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = String;
+    type Answer = u32;
+
+    fn parse(&self, data: &str) -> Result<String, Error> {
+        Ok(data.to_string())
+    }
+
+    fn part_one(&self, data: &String) -> Result<u32, Error> {
+        Ok(calculate(data, false))
+    }
+
+    fn part_two(&self, data: &String) -> Result<u32, Error> {
+        Ok(calculate(data, true))
+    }
+}
+
+fn calculate(data: &str, check_for_spelled_numbers: bool) -> u32 {
+    let mut current = 0;
+
+    for line in aoc::lines(data) {
+        let (first, last) = find_first_and_last_number(line.text, check_for_spelled_numbers);
+        let first_and_last = first + &last;
+        let first_and_last: u32 = first_and_last.parse().unwrap();
+        current += first_and_last;
+    }
+
+    current
+}
+
+fn find_first_and_last_number(line: &str, check_for_spelled_numbers: bool) -> (String, String) {
+    let mut tub = ("".to_string(), "".to_string());
+
+    // loop through the chars, slicing at their boundaries so any text works
+    for (i, _) in line.char_indices() {
+        let chars = &line[i..];
+
+        let number = get_number(chars);
+        let number: String = if check_for_spelled_numbers && number == "0" {
+            git_spelled_number(chars)
+        } else {
+            number
+        };
+
+        if number != "0" {
+            if tub.0.is_empty() {
+                tub.0 = number.clone();
+            }
+            tub.1 = number;
+        }
+    }
+
+    tub.0 = if tub.0.is_empty() {
+        "0".to_string()
+    } else {
+        tub.0
+    };
+    tub.1 = if tub.1.is_empty() {
+        "0".to_string()
+    } else {
+        tub.1
+    };
+    tub
+}
+
+fn get_number(chars: &str) -> String {
+    let all_numbers = "0123456789";
+    let mut chars = chars.chars();
+    let first_char = chars.next().unwrap();
+    if all_numbers.contains(first_char) {
+        first_char.to_string()
+    } else {
+        "0".to_string()
+    }
+}
+
+fn git_spelled_number(chars: &str) -> String {
+    let spelled_numbers = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    for (index, spelled_number) in spelled_numbers.iter().enumerate() {
+        if chars.starts_with(spelled_number) {
+            return index.to_string();
+        }
+    }
+    "0".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let data = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet"
+            .to_string();
+        let result = Day1.part_one(&data).unwrap();
+        assert_eq!(result, 142);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let data = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"
+            .to_string();
+
+        let result = Day1.part_two(&data).unwrap();
+        assert_eq!(result, 281);
+    }
+
+    #[test]
+    fn test_find_first_and_last_number() {
+        let (first, last) = find_first_and_last_number("abc123", false);
+        assert_eq!(first, "1");
+        assert_eq!(last, "3");
+    }
+
+    #[test]
+    fn test_find_first_and_last_number_with_no_numbers() {
+        let (first, last) = find_first_and_last_number("abc", false);
+        assert_eq!(first, "0");
+        assert_eq!(last, "0");
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_eq!(Day1.part_one(&"é1abc2".to_string()).unwrap(), 12);
+        assert_eq!(Day1.part_two(&"ñone2ü".to_string()).unwrap(), 12);
+    }
+}
@@ -0,0 +1,536 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use aoc::{Error, Line, Position, Solution};
+
+/// A number of dice per colour, colours without dice are left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiceCollection {
+    dice: BTreeMap<String, u32>,
+}
+
+impl DiceCollection {
+    /// Parses a single throw like ` 3 blue, 4 red`, a slice of `line`. With a
+    /// `strict` bag, colours it doesn't hold are rejected.
+    fn new(
+        line: &Line,
+        data: &str,
+        strict: Option<&DiceCollection>,
+    ) -> Result<DiceCollection, Error> {
+        let mut dice = BTreeMap::new();
+
+        let split_data = data.split(',');
+        for data in split_data {
+            let mut tokens = data.split_whitespace();
+            let num = tokens
+                .next()
+                .ok_or_else(|| line.missing(data, "number of dice"))?;
+            let num = line.number::<u32>(num)?;
+            let colour = tokens.next().ok_or_else(|| line.missing(data, "colour"))?;
+            if let Some(token) = tokens.next() {
+                return Err(line.unexpected(token, "',' or ';'"));
+            }
+            if dice.contains_key(colour) {
+                return Err(line.unexpected(colour, "a colour not already in the throw"));
+            }
+
+            if let Some(bag) = strict {
+                if !bag.dice.contains_key(colour) {
+                    let colours = bag.colours().collect::<Vec<_>>();
+                    return Err(line.unexpected(colour, format!("one of {}", colours.join(", "))));
+                }
+            }
+            dice.insert(colour.to_string(), num);
+        }
+        Ok(DiceCollection { dice })
+    }
+
+    /// The number of `colour` dice.
+    pub fn get(&self, colour: &str) -> u32 {
+        self.dice.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.dice.keys().map(String::as_str)
+    }
+
+    /// The product of the number of dice of every colour in `colours`, unless
+    /// it doesn't fit in a `u64`.
+    pub fn power<'a>(&self, mut colours: impl Iterator<Item = &'a str>) -> Option<u64> {
+        colours.try_fold(1u64, |power, colour| {
+            power.checked_mul(self.get(colour) as u64)
+        })
+    }
+}
+
+/// Lists the dice like they are written in the input, e.g. `14 blue, 12 red`.
+impl fmt::Display for DiceCollection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dice = self
+            .dice
+            .iter()
+            .map(|(colour, num)| format!("{} {}", num, colour))
+            .collect::<Vec<_>>();
+        write!(f, "{}", dice.join(", "))
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u32)> for DiceCollection {
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(iter: I) -> DiceCollection {
+        let dice = iter
+            .into_iter()
+            .map(|(colour, num)| (colour.into(), num))
+            .collect();
+        DiceCollection { dice }
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    uid: u32,
+    throws: Vec<DiceCollection>,
+    /// Where the game's line starts, to point errors at it.
+    position: Position,
+}
+
+impl Game {
+    fn new(line: &Line, strict: Option<&DiceCollection>) -> Result<Game, Error> {
+        let (uid_data, data) = line.split_once(line.text, ':')?;
+
+        let mut uid_data_split = uid_data.split_whitespace();
+        let name = uid_data_split.next().unwrap_or(uid_data);
+        line.expect(name, "Game")?;
+        let uid = uid_data_split
+            .next()
+            .ok_or_else(|| line.missing(uid_data, "game id"))?;
+        let uid = line.number::<u32>(uid)?;
+
+        let mut throws: Vec<DiceCollection> = Vec::new();
+        for data in data.split(';') {
+            let dice_throw = DiceCollection::new(line, data, strict)?;
+            throws.push(dice_throw);
+        }
+
+        Ok(Game {
+            uid,
+            throws,
+            position: line.position(line.text),
+        })
+    }
+
+    pub fn lowest_possible(&self) -> DiceCollection {
+        let mut lowest = DiceCollection::default();
+
+        for throw in &self.throws {
+            for (colour, &num) in &throw.dice {
+                let dice = lowest.dice.entry(colour.clone()).or_insert(0);
+                if num > *dice {
+                    *dice = num;
+                }
+            }
+        }
+
+        lowest
+    }
+
+    pub fn is_possible(&self, dice_throw: &DiceCollection) -> bool {
+        self.violation(dice_throw).is_none()
+    }
+
+    /// The first throw needing more dice of a colour than `bag` holds.
+    pub fn violation(&self, bag: &DiceCollection) -> Option<Violation> {
+        for (index, throw) in self.throws.iter().enumerate() {
+            for (colour, &num) in &throw.dice {
+                let limit = bag.get(colour);
+                if num > limit {
+                    return Some(Violation {
+                        throw: index + 1,
+                        colour: colour.clone(),
+                        num,
+                        limit,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A throw showing more dice of `colour` than the bag holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// 1-based index of the throw within its game.
+    pub throw: usize,
+    pub colour: String,
+    pub num: u32,
+    /// The number of `colour` dice in the bag.
+    pub limit: u32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "throw {} shows {} {}, but the bag holds {}",
+            self.throw, self.num, self.colour, self.limit
+        )
+    }
+}
+
+/// A bag to check the games against, like `small: 2 red, 3 blue`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bag {
+    /// The name before the `:`, `report` numbers the bags without one.
+    pub name: Option<String>,
+    pub dice: DiceCollection,
+}
+
+impl Bag {
+    fn new(line: &Line) -> Result<Bag, Error> {
+        let (name, dice) = match line.text.split_once(':') {
+            Some((name, dice)) if !name.trim().is_empty() => (Some(name.trim().to_string()), dice),
+            Some((name, _)) => return Err(line.missing(name, "bag name")),
+            None => (None, line.text),
+        };
+        let dice = DiceCollection::new(line, dice, None)?;
+
+        Ok(Bag { name, dice })
+    }
+}
+
+/// Parses a bag per line, the name followed by `:` is optional.
+pub fn parse_bags(data: &str) -> Result<Vec<Bag>, Error> {
+    aoc::lines(data).map(|line| Bag::new(&line)).collect()
+}
+
+/// Lists for every bag which games are possible with it, and what makes
+/// the others impossible. Bags without a name are called `bag <n>` after
+/// their position in `bags`.
+pub fn report(games: &[Game], bags: &[Bag]) -> String {
+    let mut report = String::new();
+
+    for (index, bag) in bags.iter().enumerate() {
+        let name = match &bag.name {
+            Some(name) => name.clone(),
+            None => format!("bag {}", index + 1),
+        };
+        let possible = games
+            .iter()
+            .filter(|game| game.is_possible(&bag.dice))
+            .map(|game| game.uid)
+            .collect::<Vec<_>>();
+        let ids = possible
+            .iter()
+            .map(|uid| uid.to_string())
+            .collect::<Vec<_>>();
+
+        report += &format!("{} ({})\n", name, bag.dice);
+        if possible.is_empty() {
+            report += "  possible: no games\n";
+        } else {
+            report += &format!(
+                "  possible: games {}, ids add up to {}\n",
+                ids.join(", "),
+                possible.iter().map(|&uid| uid as u64).sum::<u64>()
+            );
+        }
+        for game in games {
+            if let Some(violation) = game.violation(&bag.dice) {
+                report += &format!("  game {} is impossible: {}\n", game.uid, violation);
+            }
+        }
+    }
+    report
+}
+
+fn parse_data(data: &str, strict: Option<&DiceCollection>) -> Result<Vec<Game>, Error> {
+    let mut games: Vec<Game> = Vec::new();
+    for line in aoc::lines(data) {
+        let game = Game::new(&line, strict)?;
+        games.push(game);
+    }
+    Ok(games)
+}
+
+pub struct Day2 {
+    /// The bag the games of part one are checked against.
+    rules: DiceCollection,
+    /// Whether games may only throw colours the bag holds.
+    strict: bool,
+}
+
+impl Day2 {
+    pub fn new(rules: DiceCollection) -> Day2 {
+        Day2 {
+            rules,
+            strict: false,
+        }
+    }
+
+    /// Like `new`, but parsing fails on a throw with colours not in the bag.
+    pub fn strict(rules: DiceCollection) -> Day2 {
+        Day2 {
+            rules,
+            strict: true,
+        }
+    }
+
+    /// The bag the games of part one are checked against.
+    pub fn rules(&self) -> &DiceCollection {
+        &self.rules
+    }
+}
+
+impl Default for Day2 {
+    fn default() -> Day2 {
+        Day2::new(DiceCollection::from_iter([
+            ("blue", 14),
+            ("green", 13),
+            ("red", 12),
+        ]))
+    }
+}
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+    type Answer = u64;
+
+    fn parse(&self, data: &str) -> Result<Vec<Game>, Error> {
+        parse_data(data, self.strict.then_some(&self.rules))
+    }
+
+    /// Ids go up to `u32::MAX`, so they are added up in a `u64`.
+    fn part_one(&self, games: &Vec<Game>) -> Result<u64, Error> {
+        let mut combined_uid: u64 = 0;
+        for game in games {
+            if game.is_possible(&self.rules) {
+                combined_uid += game.uid as u64;
+            }
+        }
+
+        Ok(combined_uid)
+    }
+
+    /// The power of a game is taken over the colours of the bag, so a game
+    /// missing one of them has none.
+    fn part_two(&self, games: &Vec<Game>) -> Result<u64, Error> {
+        let mut result: u64 = 0;
+        for game in games {
+            let lowest_possible = game.lowest_possible();
+            let overflow = |quantity: String| Error::Overflow {
+                position: game.position,
+                quantity,
+            };
+            let game_score = lowest_possible
+                .power(self.rules.colours())
+                .ok_or_else(|| overflow(format!("the power of game {}", game.uid)))?;
+            result = result.checked_add(game_score).ok_or_else(|| {
+                overflow(format!("the sum of the powers up to game {}", game.uid))
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let data = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            .to_string();
+
+        let day = Day2::new(DiceCollection::from_iter([
+            ("blue", 14),
+            ("green", 13),
+            ("red", 12),
+        ]));
+        let games = day.parse(&data).unwrap();
+
+        let result = day.part_one(&games).unwrap();
+
+        assert_eq!(result, 8);
+
+        let games = day.parse("Game 4294967295: 1 red\nGame 1: 1 red").unwrap();
+        assert_eq!(day.part_one(&games).unwrap(), 4294967296);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let data = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            .to_string();
+
+        let day = Day2::default();
+        let games = day.parse(&data).unwrap();
+        let result = day.part_two(&games).unwrap();
+
+        assert_eq!(result, 2286);
+
+        let games = day
+            .parse("Game 1: 100000 red, 100000 green, 100000 blue")
+            .unwrap();
+        assert_eq!(day.part_two(&games).unwrap(), 1_000_000_000_000_000);
+
+        let data = "Game 1: 1 red, 1 green, 1 blue
+Game 2: 4294967295 red, 4294967295 green, 4294967295 blue";
+        let games = day.parse(data).unwrap();
+        assert_eq!(
+            day.part_two(&games).unwrap_err().to_string(),
+            "line 2, col 1: the power of game 2 is too large"
+        );
+    }
+
+    #[test]
+    fn test_any_colours() {
+        let data = "Game 1: 3 blue, 4 purple; 1 red, 2 gold, 6 blue
+Game 2: 1 gold; 2 gold, 1 purple
+Game 3: 8 purple, 6 red";
+        let bag = DiceCollection::from_iter([("gold", 2), ("purple", 5), ("red", 6)]);
+        let day = Day2::new(bag.clone());
+        let games = day.parse(data).unwrap();
+
+        // game 1 has blue dice the bag doesn't hold
+        assert_eq!(day.part_one(&games).unwrap(), 2);
+        let lowest = games[0].lowest_possible();
+        assert_eq!(
+            lowest,
+            DiceCollection::from_iter([("blue", 6), ("gold", 2), ("purple", 4), ("red", 1)])
+        );
+        // over gold, purple and red, so only game 1 has all of them: 2 * 4 * 1
+        assert_eq!(day.part_two(&games).unwrap(), 8);
+
+        let result = Day2::strict(bag).parse(data);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 11: expected one of gold, purple, red, got 'blue'"
+        );
+    }
+
+    #[test]
+    fn test_violation() {
+        let data = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red";
+        let day = Day2::default();
+        let games = day.parse(data).unwrap();
+
+        let violation = games[0].violation(&day.rules).unwrap();
+        assert_eq!(
+            violation,
+            Violation {
+                throw: 1,
+                colour: "red".to_string(),
+                num: 20,
+                limit: 12
+            }
+        );
+        assert_eq!(
+            violation.to_string(),
+            "throw 1 shows 20 red, but the bag holds 12"
+        );
+
+        let violation = games[1].violation(&day.rules).unwrap();
+        assert_eq!(
+            violation.to_string(),
+            "throw 3 shows 15 blue, but the bag holds 14"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let data = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+        let games = Day2::default().parse(data).unwrap();
+
+        let bags =
+            parse_bags("puzzle: 12 red, 13 green, 14 blue\n\n4 red, 4 blue, 3 green").unwrap();
+        assert_eq!(bags[0].name.as_deref(), Some("puzzle"));
+        assert_eq!(bags[1].name, None);
+
+        let expected = "puzzle (14 blue, 13 green, 12 red)
+  possible: games 1, 2, ids add up to 3
+  game 3 is impossible: throw 1 shows 20 red, but the bag holds 12
+bag 2 (4 blue, 3 green, 4 red)
+  possible: games 2, ids add up to 2
+  game 1 is impossible: throw 2 shows 6 blue, but the bag holds 4
+  game 3 is impossible: throw 1 shows 6 blue, but the bag holds 4
+";
+        assert_eq!(report(&games, &bags), expected);
+
+        // unnamed bags from separate sources are numbered together
+        let mut bags = parse_bags("12 red, 13 green, 14 blue").unwrap();
+        bags.extend(parse_bags("4 red, 4 blue, 3 green").unwrap());
+        let report = report(&games, &bags);
+        assert!(report.starts_with("bag 1 (14 blue, 13 green, 12 red)\n"));
+        assert!(report.contains("\nbag 2 (4 blue, 3 green, 4 red)\n"));
+
+        let games = Day2::default()
+            .parse("Game 4294967295: 1 red\nGame 1: 1 red")
+            .unwrap();
+        let report = super::report(&games, &bags);
+        assert!(report.contains("possible: games 4294967295, 1, ids add up to 4294967296\n"));
+
+        let result = parse_bags("puzzle: 12 red\n: 3 blue");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 1: missing bag name"
+        );
+        let result = parse_bags("12 red, 13");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 11: missing colour"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let day = Day2::default();
+
+        let result = day.parse("Game 1: 3 blue\nGame 2: x green");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 9: expected number, got 'x'"
+        );
+
+        let result = day.parse("Game 1 3 blue");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 14: missing ':'"
+        );
+
+        let result = day.parse("Gaem 1: 3 blue");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 1: expected 'Game', got 'Gaem'"
+        );
+
+        let result = day.parse("Game 1: 3 blue; 4");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 18: missing colour"
+        );
+
+        let result = day.parse("Game 1: 3 red, 40 red");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 19: expected a colour not already in the throw, got 'red'"
+        );
+        // the same colour is fine in another throw
+        assert!(day.parse("Game 1: 3 red; 40 red").is_ok());
+
+        let result = day.parse("Game 1: 3 red green");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 15: expected ',' or ';', got 'green'"
+        );
+    }
+}
@@ -1,189 +1,87 @@
-use std::fs;
+use std::env;
+use std::process;
 
-#[derive(Debug)]
-struct DiceCollection {
-    blue: u32,
-    green: u32,
-    red: u32,
-}
+use aoc::Solution;
+use day_2::{Bag, Day2, DiceCollection};
 
-impl DiceCollection {
-    fn new(data: String) -> DiceCollection {
-        let mut blue = 0;
-        let mut green = 0;
-        let mut red = 0;
+const USAGE: &str = "usage: day_2 [--strict] [--bag [<name>:]<dice>]... [--bags <file>]...";
 
-        let split_data = data.split(",");
-        for data in split_data {
-            if data.contains("blue") {
-                blue = DiceCollection::convert_to_number(data);
-            } else if data.contains("green") {
-                green = DiceCollection::convert_to_number(data);
-            } else if data.contains("red") {
-                red = DiceCollection::convert_to_number(data);
-            }
+fn main() {
+    // `--bag "12 red, 13 green, 14 blue"` and `--bags bags.txt`, with a bag
+    // per line, report which games are possible with each bag
+    let (bags, strict) = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n{}", message, USAGE);
+            process::exit(2);
         }
-        DiceCollection { blue, green, red }
+    };
+    // `--strict` rejects colours the puzzle's bag, or all given bags, don't hold
+    let rules = Day2::default().rules().clone();
+    if bags.is_empty() {
+        let day = if strict {
+            Day2::strict(rules)
+        } else {
+            Day2::new(rules)
+        };
+        aoc::run(&day, "data.txt");
+        return;
     }
 
-    fn convert_to_number(num: &str) -> u32 {
-        let red_str = num.split(" ").nth(1).unwrap();
-        red_str.parse::<u32>().unwrap()
+    let day = if strict {
+        Day2::strict(largest_bag(&bags))
+    } else {
+        Day2::new(rules)
+    };
+    match aoc::read_input("data.txt").and_then(|data| day.parse(&data)) {
+        Ok(games) => print!("{}", day_2::report(&games, &bags)),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
     }
 }
 
-#[derive(Debug)]
-struct Game {
-    uid: u32,
-    throws: Vec<DiceCollection>,
+/// The most dice of every colour in any of `bags`.
+fn largest_bag(bags: &[Bag]) -> DiceCollection {
+    let colours = bags
+        .iter()
+        .flat_map(|bag| bag.dice.colours())
+        .collect::<Vec<_>>();
+    colours
+        .iter()
+        .map(|&colour| {
+            let num = bags.iter().map(|bag| bag.dice.get(colour)).max();
+            (colour, num.unwrap_or(0))
+        })
+        .collect()
 }
 
-impl Game {
-    fn new(data: String) -> Game {
-        if data == "" {
-            panic!("Empty data");
-        }
-
-        let mut throws: Vec<DiceCollection> = Vec::new();
-        let uid_data_split = data.split(":").nth(0).unwrap();
-        let uid = uid_data_split
-            .split(" ")
-            .nth(1)
-            .unwrap()
-            .parse::<u32>()
-            .unwrap();
-
-        let data = data.split(":").nth(1).unwrap();
-
-        data.split(";").for_each(|data| {
-            let dice_throw = DiceCollection::new(data.to_string());
-            throws.push(dice_throw);
-        });
-
-        Game { uid, throws }
-    }
-
-    pub fn lowest_possible(&self) -> DiceCollection {
-        let mut blue = 0;
-        let mut green = 0;
-        let mut red = 0;
-
-        for throw in &self.throws {
-            if throw.blue > blue {
-                blue = throw.blue;
-            }
-            if throw.green > green {
-                green = throw.green;
-            }
-            if throw.red > red {
-                red = throw.red;
-            }
-        }
-
-        DiceCollection { blue, green, red }
-    }
-
-    pub fn is_possible(&self, dice_throw: &DiceCollection) -> bool {
-        for throw in &self.throws {
-            if throw.blue > dice_throw.blue
-                || throw.green > dice_throw.green
-                || throw.red > dice_throw.red
-            {
-                return false;
-            }
-        }
-        true
-    }
-}
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<(Vec<Bag>, bool), String> {
+    let mut bags = vec![];
+    let mut strict = false;
 
-fn parse_data(data: &String) -> Vec<Game> {
-    let mut games: Vec<Game> = Vec::new();
-    let lines = data.split("\n");
-    for line in lines {
-        if line == "" {
+    while let Some(arg) = args.next() {
+        if arg == "--strict" {
+            strict = true;
             continue;
         }
-        let game = Game::new(line.to_string());
-        games.push(game);
-    }
-    games
-}
-
-fn main() {
-    let data = fs::read_to_string("data.txt").expect("Unable to read file");
-    let games = parse_data(&data);
-    let rules = DiceCollection {
-        blue: 14,
-        green: 13,
-        red: 12,
-    };
-
-    let result = part_one(&games, &rules);
-    println!("{:?}", result);
-
-    let result = part_two(games);
-    println!("{:?}", result);
-}
-
-fn part_one(games: &Vec<Game>, rules: &DiceCollection) -> u32 {
-    let mut combined_uid: u32 = 0;
-    for game in games {
-        if game.is_possible(&rules) {
-            combined_uid += game.uid;
+        if arg != "--bag" && arg != "--bags" {
+            return Err(format!("unknown argument '{}'", arg));
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for '{}'", arg))?;
+
+        if arg == "--bag" {
+            let parsed = day_2::parse_bags(&value).map_err(|err| format!("--bag {}", err))?;
+            bags.extend(parsed);
+        } else {
+            let parsed = aoc::read_input(&value)
+                .and_then(|data| day_2::parse_bags(&data))
+                .map_err(|err| format!("{}: {}", value, err))?;
+            bags.extend(parsed);
         }
     }
-
-    combined_uid
-}
-
-fn part_two(games: Vec<Game>) -> u32 {
-    let mut result: u32 = 0;
-    for game in games {
-        let lowest_possible = game.lowest_possible();
-        let game_score = lowest_possible.blue * lowest_possible.green * lowest_possible.red;
-        result += game_score;
-    }
-    result
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let data = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            .to_string();
-
-        let games = parse_data(&data);
-        let rules = DiceCollection {
-            blue: 14,
-            green: 13,
-            red: 12,
-        };
-
-        let result = part_one(&games, &rules);
-
-        assert_eq!(result, 8);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let data = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            .to_string();
-
-        let games = parse_data(&data);
-        let result = part_two(games);
-
-        assert_eq!(result, 2286);
-    }
+    Ok((bags, strict))
 }
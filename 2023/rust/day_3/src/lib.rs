@@ -0,0 +1,632 @@
+use aoc::{Connectivity, Error, Point, Position, Regions, Solution};
+
+const NUMBERS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+#[derive(Debug, PartialEq)]
+enum Type {
+    Dot,
+    Symbol,
+    Gear,
+    Number,
+}
+
+/// How the chars of the schematic are read.
+#[derive(Debug, Clone)]
+pub struct Symbols {
+    /// Chars of empty space.
+    pub blanks: Vec<char>,
+    /// Symbols that can be gears.
+    pub gears: Vec<char>,
+    /// Symbols marking the numbers next to them as part numbers. Every
+    /// symbol does if `None`.
+    pub markers: Option<Vec<char>>,
+    /// How many part numbers a gear has to be next to.
+    pub gear_parts: usize,
+}
+
+impl Symbols {
+    fn get_type(&self, char: char) -> Type {
+        if NUMBERS.contains(&char) {
+            return Type::Number;
+        }
+        if self.blanks.contains(&char) {
+            return Type::Dot;
+        }
+        if self.gears.contains(&char) {
+            return Type::Gear;
+        }
+        Type::Symbol
+    }
+
+    fn marks_part(&self, char: char) -> bool {
+        matches!(self.get_type(char), Type::Symbol | Type::Gear)
+            && self
+                .markers
+                .as_ref()
+                .is_none_or(|markers| markers.contains(&char))
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Symbols {
+        Symbols {
+            blanks: vec!['.'],
+            gears: vec!['*'],
+            markers: None,
+            gear_parts: 2,
+        }
+    }
+}
+
+/// Identifies a group by where it starts, unlike its value which other
+/// groups can share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GroupId {
+    row: u32,
+    start_pos: u32,
+}
+
+#[derive(Debug)]
+struct Group {
+    start_pos: u32,
+    value: String,
+    index: u32,
+    /// The value of a number, parsed along with the grid.
+    number: Option<u32>,
+}
+
+impl Group {
+    /// Groups are never empty, and all of their chars are of the same type.
+    fn first_char(&self) -> char {
+        self.value.chars().next().unwrap()
+    }
+
+    fn id(&self) -> GroupId {
+        GroupId {
+            row: self.index,
+            start_pos: self.start_pos,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Grid {
+    /// Every group in reading order of its first char.
+    groups: Vec<Group>,
+    /// The index of the group every cell belongs to.
+    owners: aoc::Grid<usize>,
+    /// The input line of every row, to point errors at a group.
+    lines: Vec<usize>,
+}
+
+impl Grid {
+    /// Runs of digits make up one number, every other char is a group of its
+    /// own, so neighbouring symbols are never mistaken for one.
+    pub fn from_data(data: &str) -> Result<Grid, Error> {
+        let cells = aoc::Grid::parse(data, Some)?;
+        let numbers = Regions::matching(&cells, Connectivity::Horizontal, char::is_ascii_digit);
+        // the grid skips empty lines, so its rows need their line numbers
+        let lines = aoc::lines(data).map(|line| line.number).collect::<Vec<_>>();
+
+        let mut groups = vec![];
+        let mut owners = cells.map(|_| 0);
+        for (point, &char) in cells.iter() {
+            let (value, number) = match numbers.at(point) {
+                Some(region) if region.cells[0] != point => {
+                    owners[point] = owners[region.cells[0]];
+                    continue;
+                }
+                Some(region) => {
+                    let value = region
+                        .cells
+                        .iter()
+                        .map(|&cell| cells[cell])
+                        .collect::<String>();
+                    // only digits, so the number can only be too long
+                    let number = value.parse::<u32>().map_err(|_| Error::Overflow {
+                        position: position(&lines, point),
+                        quantity: format!("the number {}", value),
+                    })?;
+                    (value, Some(number))
+                }
+                None => (char.to_string(), None),
+            };
+
+            owners[point] = groups.len();
+            groups.push(Group {
+                start_pos: point.col as u32,
+                value,
+                index: point.row as u32,
+                number,
+            });
+        }
+
+        Ok(Grid {
+            groups,
+            owners,
+            lines,
+        })
+    }
+
+    /// Where `group` starts in the input.
+    fn position(&self, group: &Group) -> Position {
+        let id = group.id();
+        position(
+            &self.lines,
+            Point::new(id.row as usize, id.start_pos as usize),
+        )
+    }
+
+    fn get_groups_of_type(&self, symbols: &Symbols, group_type: Type) -> Vec<&Group> {
+        self.groups
+            .iter()
+            .filter(|group| symbols.get_type(group.first_char()) == group_type)
+            .collect()
+    }
+
+    /// Every other group sharing a side or a corner with `group`, each one
+    /// once and in reading order.
+    fn get_surrounding_groups(&self, group: &Group) -> Vec<&Group> {
+        let id = group.id();
+        let row = id.row as usize;
+        let start = id.start_pos as usize;
+        let own = self.owners[Point::new(row, start)];
+
+        let mut indices = (start..start + group.value.chars().count())
+            .flat_map(|col| self.owners.neighbours8(Point::new(row, col)))
+            .map(|point| self.owners[point])
+            .filter(|&index| index != own)
+            .collect::<Vec<_>>();
+        indices.sort();
+        indices.dedup();
+
+        indices
+            .into_iter()
+            .map(|index| &self.groups[index])
+            .collect()
+    }
+}
+
+/// Where the cell at `point` is in the input, with `lines` the line number of
+/// every row.
+fn position(lines: &[usize], point: Point) -> Position {
+    Position {
+        line: lines[point.row],
+        column: point.col + 1,
+    }
+}
+
+/// How `Day3::annotate` highlights the schematic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    /// ANSI colours for the terminal.
+    Ansi,
+    /// A standalone HTML page, for inputs too large to read in a terminal.
+    Html,
+}
+
+/// What a group turned out to be in the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    PartNumber,
+    /// A number without a symbol next to it.
+    Orphan,
+    /// A gear next to the right amount of part numbers.
+    Gear,
+    Other,
+}
+
+#[derive(Default)]
+pub struct Day3 {
+    symbols: Symbols,
+}
+
+impl Day3 {
+    pub fn new(symbols: Symbols) -> Day3 {
+        Day3 { symbols }
+    }
+
+    fn is_part_number(&self, grid: &Grid, number: &Group) -> bool {
+        grid.get_surrounding_groups(number)
+            .iter()
+            .any(|x| self.symbols.marks_part(x.first_char()))
+    }
+
+    /// The part numbers next to `gear`, if there are as many as a gear needs.
+    fn gear_numbers<'a>(&self, grid: &'a Grid, gear: &Group) -> Option<Vec<&'a Group>> {
+        let number_nabors = grid
+            .get_surrounding_groups(gear)
+            .into_iter()
+            .filter(|x| self.symbols.get_type(x.first_char()) == Type::Number)
+            .filter(|x| self.is_part_number(grid, x))
+            .collect::<Vec<_>>();
+
+        (number_nabors.len() == self.symbols.gear_parts).then_some(number_nabors)
+    }
+
+    fn role(&self, grid: &Grid, group: &Group) -> Role {
+        match self.symbols.get_type(group.first_char()) {
+            Type::Number if self.is_part_number(grid, group) => Role::PartNumber,
+            Type::Number => Role::Orphan,
+            Type::Gear if self.gear_numbers(grid, group).is_some() => Role::Gear,
+            _ => Role::Other,
+        }
+    }
+
+    /// Renders the schematic with part numbers in green, numbers that aren't
+    /// parts in red, and gears counted by part two in yellow.
+    pub fn annotate(&self, grid: &Grid, highlight: Highlight) -> String {
+        let mut output = String::new();
+        if highlight == Highlight::Html {
+            output += "<!DOCTYPE html>\n<style>\n";
+            output += "  .part { color: green; }\n";
+            output += "  .orphan { color: red; }\n";
+            output += "  .gear { color: goldenrod; font-weight: bold; }\n";
+            output += "</style>\n<pre>\n";
+        }
+
+        for (index, group) in grid.groups.iter().enumerate() {
+            if index > 0 && group.start_pos == 0 {
+                output.push('\n');
+            }
+
+            let role = self.role(grid, group);
+            match highlight {
+                Highlight::Ansi => {
+                    let colour = match role {
+                        Role::PartNumber => "32",
+                        Role::Orphan => "31",
+                        Role::Gear => "33",
+                        Role::Other => {
+                            output += &group.value;
+                            continue;
+                        }
+                    };
+                    output += &format!("\x1b[{}m{}\x1b[0m", colour, group.value);
+                }
+                Highlight::Html => {
+                    let value = escape_html(&group.value);
+                    let class = match role {
+                        Role::PartNumber => "part",
+                        Role::Orphan => "orphan",
+                        Role::Gear => "gear",
+                        Role::Other => {
+                            output += &value;
+                            continue;
+                        }
+                    };
+                    output += &format!("<span class=\"{}\">{}</span>", class, value);
+                }
+            }
+        }
+
+        output.push('\n');
+        if highlight == Highlight::Html {
+            output += "</pre>\n";
+        }
+        output
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl Solution for Day3 {
+    type Input = Grid;
+    type Answer = u64;
+
+    fn parse(&self, data: &str) -> Result<Grid, Error> {
+        Grid::from_data(data)
+    }
+
+    fn part_one(&self, grid: &Grid) -> Result<u64, Error> {
+        let all_numbers = grid.get_groups_of_type(&self.symbols, Type::Number);
+        let mut result: u64 = 0;
+
+        for number in all_numbers {
+            if self.is_part_number(grid, number) {
+                let value = number.number.unwrap_or(0) as u64;
+                result = result.checked_add(value).ok_or_else(|| Error::Overflow {
+                    position: grid.position(number),
+                    quantity: "the sum of the part numbers up to this one".to_string(),
+                })?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn part_two(&self, grid: &Grid) -> Result<u64, Error> {
+        let all_gears = grid.get_groups_of_type(&self.symbols, Type::Gear);
+        let mut result: u64 = 0;
+
+        for gear in all_gears {
+            if let Some(numbers) = self.gear_numbers(grid, gear) {
+                // gears can need any number of parts, so even one ratio can
+                // be too large
+                let overflow = |quantity: &str| Error::Overflow {
+                    position: grid.position(gear),
+                    quantity: quantity.to_string(),
+                };
+                let ratio = numbers
+                    .iter()
+                    .try_fold(1u64, |ratio, number| {
+                        ratio.checked_mul(number.number.unwrap_or(0) as u64)
+                    })
+                    .ok_or_else(|| overflow("the ratio of the gear"))?;
+                result = result
+                    .checked_add(ratio)
+                    .ok_or_else(|| overflow("the sum of the gear ratios up to this one"))?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let day = Day3::default();
+        let data = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."
+            .to_string();
+
+        let grid = day.parse(&data).unwrap();
+        let result = day.part_one(&grid).unwrap();
+
+        assert_eq!(result, 4361);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let day = Day3::default();
+        let data = "467..114.
+...*.....
+..35..633
+......#..
+617*.....
+.....+.58
+..592....
+......755
+...$.*...
+.664.598."
+            .to_string();
+
+        let grid = day.parse(&data).unwrap();
+        let result = day.part_two(&grid).unwrap();
+
+        assert_eq!(result, 467835);
+    }
+
+    fn surrounding(grid: &Grid, row: u32, start_pos: u32) -> Vec<(&str, GroupId)> {
+        let group = grid
+            .groups
+            .iter()
+            .find(|group| group.id() == GroupId { row, start_pos })
+            .unwrap();
+        grid.get_surrounding_groups(group)
+            .into_iter()
+            .map(|group| (group.value.as_str(), group.id()))
+            .collect()
+    }
+
+    fn id(row: u32, start_pos: u32) -> GroupId {
+        GroupId { row, start_pos }
+    }
+
+    #[test]
+    fn test_surrounding_repeated_values() {
+        let day = Day3::default();
+        let grid = day.parse("12.12\n..*..\n12.12").unwrap();
+        let numbers = surrounding(&grid, 1, 2)
+            .into_iter()
+            .filter(|(value, _)| *value == "12")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            numbers,
+            [
+                ("12", id(0, 0)),
+                ("12", id(0, 3)),
+                ("12", id(2, 0)),
+                ("12", id(2, 3))
+            ]
+        );
+        // four numbers around it, so it's no gear
+        assert_eq!(day.part_two(&grid).unwrap(), 0);
+        assert_eq!(day.part_one(&grid).unwrap(), 48);
+
+        let grid = day.parse("12*12\n.....").unwrap();
+        assert_eq!(day.part_two(&grid).unwrap(), 144);
+
+        let grid = day.parse("7.7\n.*.\n...").unwrap();
+        assert_eq!(day.part_two(&grid).unwrap(), 49);
+    }
+
+    #[test]
+    fn test_surrounding_unique() {
+        let day = Day3::default();
+        let grid = day.parse("467..114..\n...*......\n..35..633.").unwrap();
+        for group in &grid.groups {
+            let mut ids = surrounding(&grid, group.index, group.start_pos)
+                .into_iter()
+                .map(|(_, id)| id)
+                .collect::<Vec<_>>();
+            assert!(!ids.contains(&group.id()));
+
+            let count = ids.len();
+            ids.dedup();
+            assert_eq!(ids.len(), count, "{:?}", group);
+        }
+    }
+
+    #[test]
+    fn test_surrounding_corners_and_edges() {
+        let day = Day3::default();
+        let grid = day.parse("*1.\n...\n.2*").unwrap();
+        assert_eq!(
+            surrounding(&grid, 0, 0),
+            [("1", id(0, 1)), (".", id(1, 0)), (".", id(1, 1))]
+        );
+        assert_eq!(
+            surrounding(&grid, 2, 2),
+            [(".", id(1, 1)), (".", id(1, 2)), ("2", id(2, 1))]
+        );
+        assert_eq!(day.part_one(&grid).unwrap(), 3);
+
+        // numbers touching the grid's right edge and bottom corners
+        let grid = day.parse("..12\n.#..\n5..9").unwrap();
+        assert_eq!(day.part_one(&grid).unwrap(), 17);
+
+        let grid = day.parse("1").unwrap();
+        assert_eq!(surrounding(&grid, 0, 0), []);
+        assert_eq!(day.part_one(&grid).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_adjacent_symbols() {
+        let day = Day3::default();
+        // each `*` is a gear of its own, touching one number only
+        let grid = day.parse("5**6\n....").unwrap();
+        assert_eq!(
+            surrounding(&grid, 0, 1)[..2],
+            [("5", id(0, 0)), ("*", id(0, 2))]
+        );
+        assert_eq!(day.part_one(&grid).unwrap(), 11);
+        assert_eq!(day.part_two(&grid).unwrap(), 0);
+
+        let grid = day.parse("1.\n**\n.2").unwrap();
+        assert_eq!(day.part_two(&grid).unwrap(), 4);
+
+        let expected = "\x1b[32m5\x1b[0m**\x1b[32m6\x1b[0m\n....\n";
+        assert_eq!(
+            day.annotate(&day.parse("5**6\n....").unwrap(), Highlight::Ansi),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_symbols() {
+        let data = "1#2.3
+.....
+4*5*6
+..7..";
+        let day = Day3::default();
+        let grid = day.parse(data).unwrap();
+        assert_eq!(day.part_one(&grid).unwrap(), 25);
+        // both gears touch 3 numbers
+        assert_eq!(day.part_two(&grid).unwrap(), 0);
+
+        // `#` is just more empty space
+        let day = Day3::new(Symbols {
+            blanks: vec!['.', '#'],
+            ..Symbols::default()
+        });
+        assert_eq!(day.part_one(&grid).unwrap(), 22);
+
+        // only `*` marks parts, and gears need exactly 3 of them
+        let day = Day3::new(Symbols {
+            markers: Some(vec!['*']),
+            gear_parts: 3,
+            ..Symbols::default()
+        });
+        assert_eq!(day.part_one(&grid).unwrap(), 22);
+        assert_eq!(day.part_two(&grid).unwrap(), 4 * 5 * 7 + 5 * 6 * 7);
+
+        // `#` gears, which don't count as markers, so the numbers next to
+        // them aren't parts unless a `*` touches them as well
+        let day = Day3::new(Symbols {
+            gears: vec!['#'],
+            markers: Some(vec!['*']),
+            gear_parts: 2,
+            ..Symbols::default()
+        });
+        assert_eq!(day.part_two(&grid).unwrap(), 0);
+        assert_eq!(day.part_two(&day.parse("1#2\n*.*").unwrap()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_annotate() {
+        let day = Day3::default();
+        let grid = day.parse("12*3..\n......\n4..&*5").unwrap();
+
+        let expected = "\x1b[32m12\x1b[0m\x1b[33m*\x1b[0m\x1b[32m3\x1b[0m..
+......
+\x1b[31m4\x1b[0m..&*\x1b[32m5\x1b[0m
+";
+        assert_eq!(day.annotate(&grid, Highlight::Ansi), expected);
+
+        let html = day.annotate(&grid, Highlight::Html);
+        let expected = "<pre>
+<span class=\"part\">12</span><span class=\"gear\">*</span><span class=\"part\">3</span>..
+......
+<span class=\"orphan\">4</span>..&amp;*<span class=\"part\">5</span>
+</pre>
+";
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with(expected), "{}", html);
+    }
+
+    #[test]
+    fn test_uneven_rows() {
+        let day = Day3::default();
+        let data = "467..114..
+...*......
+..35..633";
+
+        let result = day.parse(data);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 3, col 10: expected 10 columns, got '9 columns'"
+        );
+    }
+
+    #[test]
+    fn test_large_gear_ratios() {
+        let day = Day3::new(Symbols {
+            gear_parts: 3,
+            ..Symbols::default()
+        });
+
+        // six gears with a ratio of 999^3 each, too much for a `u32`
+        let data = ["999*999\n..999.."; 6].join("\n.......\n");
+        let grid = day.parse(&data).unwrap();
+        assert_eq!(day.part_two(&grid).unwrap(), 6 * 999 * 999 * 999);
+
+        let grid = day
+            .parse("4294967295*4294967295\n..........4294967295.")
+            .unwrap();
+        assert_eq!(
+            day.part_two(&grid).unwrap_err().to_string(),
+            "line 1, col 11: the ratio of the gear is too large"
+        );
+        assert_eq!(day.part_one(&grid).unwrap(), 3 * 4294967295);
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        let day = Day3::default();
+        let grid = day.parse("4294967295*").unwrap();
+        assert_eq!(day.part_one(&grid).unwrap(), 4294967295);
+
+        let result = day.parse("..............\n\n.*12345678901.");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 3, col 3: the number 12345678901 is too large"
+        );
+    }
+}
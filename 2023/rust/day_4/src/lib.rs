@@ -0,0 +1,599 @@
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use aoc::{Error, Line, Position, Solution};
+
+/// How the matches on a card turn into points.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Scoring {
+    /// 1 point for the first match, doubled for every match after it.
+    #[default]
+    Doubling,
+    /// 1 point per match.
+    Linear,
+    /// The points for `n` matches at index `n`. More matches than the table
+    /// covers score its last entry.
+    Table(Vec<u64>),
+}
+
+impl Scoring {
+    /// The points for `matches` matches, unless they don't fit in a `u64`.
+    pub fn points(&self, matches: u32) -> Option<u64> {
+        match self {
+            Scoring::Doubling if matches == 0 => Some(0),
+            Scoring::Doubling => 2u64.checked_pow(matches - 1),
+            Scoring::Linear => Some(matches as u64),
+            Scoring::Table(table) => Some(
+                table
+                    .get(matches as usize)
+                    .or(table.last())
+                    .copied()
+                    .unwrap_or(0),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Card {
+    /// The number after `Card`.
+    id: u32,
+    winners: HashSet<u32>,
+    numbers: HashSet<u32>,
+    /// How many numbers are winners, counted the first time it's needed.
+    matches: OnceCell<u32>,
+    /// Where the card's line starts, to point errors at it.
+    position: Position,
+}
+
+impl Card {
+    fn from_data(line: &Line) -> Result<Card, Error> {
+        let (name, winners_and_numbers) = line.split_once(line.text, ':')?; // second part of the data containing the `winners` and `numbers`
+        let id = Card::read_id(line, name)?; // first part of the data containing the `Card` name and id
+        let (winners, numbers) = line.split_once(winners_and_numbers, '|')?; // split the winners and numbers into two parts
+
+        let winners = line.numbers::<u32>(winners)?; // extract and parse the first part: winners
+        let numbers = line.numbers::<u32>(numbers)?; // extract and parse the second part: numbers
+
+        Ok(Card {
+            id,
+            numbers: numbers.into_iter().collect(),
+            winners: winners.into_iter().collect(),
+            matches: OnceCell::new(),
+            position: line.position(line.text),
+        })
+    }
+
+    /// Parses the `Card 12` part of a line.
+    fn read_id(line: &Line, name: &str) -> Result<u32, Error> {
+        let mut tokens = name.split_whitespace();
+        line.expect(tokens.next().unwrap_or(name), "Card")?;
+
+        let id = tokens
+            .next()
+            .ok_or_else(|| line.missing(name.trim_end(), "card id"))?;
+        if let Some(token) = tokens.next() {
+            return Err(line.unexpected(token, "':'"));
+        }
+        line.number(id)
+    }
+
+    pub fn get_num_of_winners(&self) -> u32 {
+        *self
+            .matches
+            .get_or_init(|| self.winners.intersection(&self.numbers).count() as u32)
+    }
+
+    pub fn get_points(&self, scoring: &Scoring) -> Option<u64> {
+        scoring.points(self.get_num_of_winners())
+    }
+
+    /// The ids of the cards this card wins a copy of, the next ones up. Ids
+    /// past `u32::MAX` are left out, parsing rejects cards that win them.
+    fn won_ids(&self) -> impl Iterator<Item = u32> {
+        (self.id..=u32::MAX)
+            .skip(1)
+            .take(self.get_num_of_winners() as usize)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CardCollection {
+    /// The cards by id, whatever order they were listed in.
+    cards: BTreeMap<u32, Card>,
+}
+
+impl CardCollection {
+    fn from_data(data: &str) -> Result<CardCollection, Error> {
+        let mut cards: BTreeMap<u32, Card> = BTreeMap::new();
+        let mut lines: BTreeMap<u32, Line> = BTreeMap::new();
+
+        for line in aoc::lines(data) {
+            let card = Card::from_data(&line)?;
+            if let Some(first) = lines.get(&card.id) {
+                let (name, _) = line.split_once(line.text, ':')?;
+                let id = name.split_whitespace().nth(1).unwrap_or(name);
+                return Err(
+                    line.unexpected(id, format!("a card id not used on line {}", first.number))
+                );
+            }
+            if card.id.checked_add(card.get_num_of_winners()).is_none() {
+                return Err(Error::Overflow {
+                    position: line.position(line.text),
+                    quantity: format!("the id of the last card won by card {}", card.id),
+                });
+            }
+            lines.insert(card.id, line);
+            cards.insert(card.id, card);
+        }
+
+        // copies of cards past the last one are never won, but every card in
+        // between has to be there
+        let last = cards.keys().next_back().copied().unwrap_or(0);
+        for card in cards.values() {
+            for id in card.won_ids().take_while(|&id| id <= last) {
+                if !cards.contains_key(&id) {
+                    let line = lines[&card.id];
+                    return Err(Error::MissingSection {
+                        position: line.position(line.text),
+                        section: format!("card {} won by card {}", id, card.id),
+                    });
+                }
+            }
+        }
+
+        Ok(CardCollection { cards })
+    }
+
+    /// Plays out the copies won by every card, recording which card won
+    /// copies of which other card if `record` is set.
+    fn play(&self, record: bool) -> Result<Cascade, Overflow> {
+        let mut instances = self
+            .cards
+            .keys()
+            .map(|&id| (id, 1))
+            .collect::<BTreeMap<u32, u64>>();
+        let mut trace = record.then(Vec::new);
+        let mut total: u64 = 0;
+
+        for card in self.cards.values() {
+            // all copies of the card have been won by now
+            let copies = instances[&card.id];
+            total = total
+                .checked_add(copies)
+                .ok_or(Overflow { card: card.id })?;
+
+            // every instance of the card wins one copy of each of the next cards
+            for id in card.won_ids() {
+                let Some(won) = instances.get_mut(&id) else {
+                    // only cards past the last one are missing
+                    break;
+                };
+                *won = won.checked_add(copies).ok_or(Overflow { card: id })?;
+                if let Some(trace) = &mut trace {
+                    trace.push(Transfer {
+                        from: card.id,
+                        to: id,
+                        copies,
+                    });
+                }
+            }
+        }
+
+        Ok(Cascade {
+            instances,
+            trace,
+            total,
+        })
+    }
+
+    /// How many instances of each card there are once all copies are won.
+    /// Copies grow exponentially with the length of the deck, so they can
+    /// overflow.
+    pub fn cascade(&self) -> Result<Cascade, Error> {
+        self.play(false).map_err(|overflow| self.overflow(overflow))
+    }
+
+    /// Like `cascade`, but also records every copy won.
+    pub fn trace(&self) -> Result<Cascade, Error> {
+        self.play(true).map_err(|overflow| self.overflow(overflow))
+    }
+
+    fn overflow(&self, Overflow { card }: Overflow) -> Error {
+        Error::Overflow {
+            position: self.cards[&card].position,
+            quantity: format!("the number of copies of card {}", card),
+        }
+    }
+
+    /// The points of all cards together. Doubling points grow exponentially
+    /// with the matches, so they can overflow for a single card.
+    fn get_points(&self, scoring: &Scoring) -> Result<u64, Error> {
+        let mut points: u64 = 0;
+        for card in self.cards.values() {
+            let quantity = match card.get_points(scoring) {
+                Some(card_points) => match points.checked_add(card_points) {
+                    Some(sum) => {
+                        points = sum;
+                        continue;
+                    }
+                    None => format!("the score of the cards up to card {}", card.id),
+                },
+                None => format!("the score of card {}", card.id),
+            };
+            return Err(Error::Overflow {
+                position: card.position,
+                quantity,
+            });
+        }
+        Ok(points)
+    }
+}
+
+/// Copies of card `to` won by all instances of card `from`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transfer {
+    pub from: u32,
+    pub to: u32,
+    pub copies: u64,
+}
+
+/// The instances of `card`, or of all cards up to it, don't fit in a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Overflow {
+    card: u32,
+}
+
+/// The outcome of scratching every card, including the copies won.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cascade {
+    /// Instances of every card by id, the original included.
+    instances: BTreeMap<u32, u64>,
+    trace: Option<Vec<Transfer>>,
+    /// Instances of all cards together.
+    total: u64,
+}
+
+impl Cascade {
+    pub fn instances(&self) -> &BTreeMap<u32, u64> {
+        &self.instances
+    }
+
+    /// Every copy won in the order they were won, if recorded.
+    pub fn trace(&self) -> Option<&[Transfer]> {
+        self.trace.as_deref()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// A table with a row per card, listing the copies it won if recorded.
+impl fmt::Display for Cascade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "card  instances")?;
+        if self.trace.is_some() {
+            write!(f, "  copies won")?;
+        }
+        writeln!(f)?;
+
+        for (id, instances) in &self.instances {
+            write!(f, "{:>4}  {:>9}", id, instances)?;
+            if let Some(trace) = &self.trace {
+                let won = trace
+                    .iter()
+                    .filter(|transfer| transfer.from == *id)
+                    .map(|transfer| format!("{} of card {}", transfer.copies, transfer.to))
+                    .collect::<Vec<_>>();
+                if !won.is_empty() {
+                    write!(f, "  {}", won.join(", "))?;
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "total {:>9}", self.total())
+    }
+}
+
+#[derive(Default)]
+pub struct Day4 {
+    /// How part one scores the cards.
+    scoring: Scoring,
+}
+
+impl Day4 {
+    pub fn new(scoring: Scoring) -> Day4 {
+        Day4 { scoring }
+    }
+}
+
+impl Solution for Day4 {
+    type Input = CardCollection;
+    type Answer = u64;
+
+    fn parse(&self, data: &str) -> Result<CardCollection, Error> {
+        CardCollection::from_data(data)
+    }
+
+    fn part_one(&self, collection: &CardCollection) -> Result<u64, Error> {
+        collection.get_points(&self.scoring)
+    }
+
+    fn part_two(&self, collection: &CardCollection) -> Result<u64, Error> {
+        Ok(collection.cascade()?.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let day = Day4::default();
+        let data = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+            .to_string();
+
+        let collection = day.parse(&data).unwrap();
+        let result = day.part_one(&collection).unwrap();
+
+        assert_eq!(result, 13);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let day = Day4::default();
+        let data = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+            .to_string();
+
+        let collection = day.parse(&data).unwrap();
+        let result = day.part_two(&collection).unwrap();
+
+        assert_eq!(result, 30);
+    }
+
+    #[test]
+    fn test_scoring() {
+        let data = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        // the cards have 4, 2, 2, 1, 0 and 0 matches, and are parsed once
+        // for every scoring
+        let collection = Day4::default().parse(data).unwrap();
+        let points = |scoring: Scoring| Day4::new(scoring).part_one(&collection).unwrap();
+
+        assert_eq!(points(Scoring::Doubling), 8 + 2 + 2 + 1);
+        assert_eq!(points(Scoring::Linear), 4 + 2 + 2 + 1);
+
+        let triangle = Scoring::Table(vec![0, 1, 3, 6]);
+        assert_eq!(points(triangle), 6 + 3 + 3 + 1);
+        assert_eq!(Scoring::Table(vec![]).points(3), Some(0));
+    }
+
+    /// A card where the first `matches` of its `len` numbers are winners.
+    fn card(id: u32, matches: u32, len: u32) -> String {
+        let winners = (1..=matches).map(|n| n.to_string()).collect::<Vec<_>>();
+        let numbers = (1..=len).map(|n| n.to_string()).collect::<Vec<_>>();
+        format!("Card {}: {} | {}", id, winners.join(" "), numbers.join(" "))
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let day = Day4::default();
+        assert_eq!(Scoring::Doubling.points(64), Some(1 << 63));
+        assert_eq!(Scoring::Doubling.points(65), None);
+
+        // the cards past the last one aren't around to win copies of
+        let data = format!("{}\n{}", card(99, 1, 1), card(100, 64, 64));
+        let collection = day.parse(&data).unwrap();
+        assert_eq!(day.part_one(&collection).unwrap(), (1 << 63) + 1);
+
+        let collection = day.parse(&card(1, 70, 70)).unwrap();
+        assert_eq!(
+            day.part_one(&collection).unwrap_err().to_string(),
+            "line 1, col 1: the score of card 1 is too large"
+        );
+        // the same deck scores fine one point per match
+        assert_eq!(
+            Day4::new(Scoring::Linear).part_one(&collection).unwrap(),
+            70
+        );
+
+        let data = format!("{}\n{}", card(99, 64, 64), card(100, 64, 64));
+        let collection = day.parse(&data).unwrap();
+        assert_eq!(
+            day.part_one(&collection).unwrap_err().to_string(),
+            "line 2, col 1: the score of the cards up to card 100 is too large"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_numbers() {
+        // every winning number counts once, however often it's drawn
+        let day = Day4::default();
+        let collection = day.parse("Card 1: 41 48 41 | 41 41 48 9").unwrap();
+        assert_eq!(collection.cards[&1].get_num_of_winners(), 2);
+        assert_eq!(day.part_one(&collection).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let day = Day4::default();
+        let data = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let collection = day.parse(data).unwrap();
+
+        let cascade = collection.cascade().unwrap();
+        let instances = cascade.instances().values().copied().collect::<Vec<_>>();
+        assert_eq!(instances, [1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.trace(), None);
+
+        let cascade = collection.trace().unwrap();
+        let trace = cascade.trace().unwrap();
+        assert_eq!(trace.len(), 4 + 2 + 2 + 1);
+        assert_eq!(
+            trace[7],
+            Transfer {
+                from: 3,
+                to: 5,
+                copies: 4
+            }
+        );
+        // the copies won of a card add up to its instances
+        for (id, instances) in cascade.instances() {
+            let won = trace
+                .iter()
+                .filter(|transfer| transfer.to == *id)
+                .map(|transfer| transfer.copies)
+                .sum::<u64>();
+            assert_eq!(won + 1, *instances);
+        }
+
+        let expected = "card  instances  copies won
+   1          1  1 of card 2, 1 of card 3, 1 of card 4, 1 of card 5
+   2          2  2 of card 3, 2 of card 4
+   3          4  4 of card 4, 4 of card 5
+   4          8  8 of card 5
+   5         14
+   6          1
+total        30
+";
+        assert_eq!(cascade.to_string(), expected);
+    }
+
+    #[test]
+    fn test_card_ids() {
+        let day = Day4::default();
+
+        // the example shuffled, so card 4 comes first
+        let data = "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1";
+        let collection = day.parse(data).unwrap();
+        assert_eq!(day.part_one(&collection).unwrap(), 13);
+        assert_eq!(day.part_two(&collection).unwrap(), 30);
+
+        // no card wins copies of cards 2 and 3, so they can be left out
+        let data = "Card 1: 1 | 2
+Card 4: 1 2 | 1 2
+Card 5: 1 | 2
+Card 6: 1 | 2";
+        let collection = day.parse(data).unwrap();
+        let instances = collection.cascade().unwrap().instances().clone();
+        assert_eq!(instances, BTreeMap::from([(1, 1), (4, 1), (5, 2), (6, 2)]));
+
+        let result = day.parse("Card 1: 1 2 | 1 2\nCard 3: 1 | 2");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 1: missing card 2 won by card 1"
+        );
+
+        let result = day.parse("Card 1: 1 | 2\nCard 2: 1 | 2\nCard  1: 1 | 2");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 3, col 7: expected a card id not used on line 1, got '1'"
+        );
+
+        // the last possible card can't win anything
+        let collection = day.parse("Card 4294967295: 1 | 2").unwrap();
+        assert_eq!(day.part_two(&collection).unwrap(), 1);
+        let result = day.parse("Card 4294967294: 1 | 1\nCard 4294967295: 1 | 1");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 1: the id of the last card won by card 4294967295 is too large"
+        );
+    }
+
+    /// A deck of `len` cards where every card wins a copy of all later cards,
+    /// so card `n` ends up with `2^(n - 1)` instances.
+    fn doubling_deck(len: u32) -> String {
+        (1..=len)
+            .map(|id| {
+                let numbers = (1..=len - id).map(|n| n.to_string()).collect::<Vec<_>>();
+                let numbers = numbers.join(" ");
+                format!("Card {}: {} | {}", id, numbers, numbers)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_overflow() {
+        let day = Day4::default();
+
+        // `2^64 - 1` instances in total is just about countable
+        let collection = day.parse(&doubling_deck(64)).unwrap();
+        let cascade = collection.cascade().unwrap();
+        assert_eq!(cascade.instances()[&64], 1 << 63);
+        assert_eq!(day.part_two(&collection).unwrap(), u64::MAX);
+        assert_eq!(day.part_one(&collection).unwrap(), (1 << 63) - 1);
+
+        // the deck itself is fine, only its copies can't be counted
+        let collection = day.parse(&doubling_deck(65)).unwrap();
+        let expected = "line 65, col 1: the number of copies of card 65 is too large";
+        let result = day.part_two(&collection);
+        assert_eq!(result.unwrap_err().to_string(), expected);
+        assert_eq!(collection.trace().unwrap_err().to_string(), expected);
+
+        let collection = CardCollection {
+            cards: BTreeMap::new(),
+        };
+        assert_eq!(collection.cascade().unwrap().total(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let day = Day4::default();
+        let result = day.parse("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 3O");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 20: expected number, got '3O'"
+        );
+
+        let result = day.parse("Card 1: 41 48 83 86");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 20: missing '|'"
+        );
+
+        let result = day.parse("Card: 41 48 | 83 86");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 5: missing card id"
+        );
+
+        let result = day.parse("Crad 1: 41 48 | 83 86");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 1: expected 'Card', got 'Crad'"
+        );
+
+        let result = day.parse("Card 1a: 41 48 | 83 86");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 6: expected number, got '1a'"
+        );
+    }
+}
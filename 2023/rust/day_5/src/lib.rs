@@ -0,0 +1,1090 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::ops::Range;
+
+use aoc::{Error, Line, Position, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ConvertInfo {
+    destination: u64,
+    source: u64,
+    range: u64,
+}
+
+/// Something odd about the ranges of a single map, found while parsing it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+    /// Two entries claim the same source values, so only the later one applies.
+    OverlappingSources {
+        map: String,
+        lines: (usize, usize),
+        overlap: Range<u64>,
+    },
+    /// Two entries produce the same destination values, so a location can
+    /// come from several seeds.
+    OverlappingDestinations {
+        map: String,
+        lines: (usize, usize),
+        overlap: Range<u64>,
+    },
+    /// Source values between two entries that no entry maps; they convert
+    /// to themselves.
+    Gap { map: String, gap: Range<u64> },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::OverlappingSources {
+                map,
+                lines,
+                overlap,
+            } => write!(
+                f,
+                "{}: lines {} and {} overlap in source range {}..{}",
+                map, lines.0, lines.1, overlap.start, overlap.end
+            ),
+            Issue::OverlappingDestinations {
+                map,
+                lines,
+                overlap,
+            } => write!(
+                f,
+                "{}: lines {} and {} overlap in destination range {}..{}",
+                map, lines.0, lines.1, overlap.start, overlap.end
+            ),
+            Issue::Gap { map, gap } => {
+                write!(
+                    f,
+                    "{}: source range {}..{} is not mapped",
+                    map, gap.start, gap.end
+                )
+            }
+        }
+    }
+}
+
+/// The overlap of two half-open ranges, if they share any value.
+fn overlap(first: Range<u64>, second: Range<u64>) -> Option<Range<u64>> {
+    let overlap = first.start.max(second.start)..first.end.min(second.end);
+    if overlap.is_empty() {
+        None
+    } else {
+        Some(overlap)
+    }
+}
+
+/// Checks the entries of the map `name`, each paired with the line it was
+/// read from, for overlapping ranges and gaps. `entries` are sorted by source.
+fn validate(name: &str, entries: &[(Line, ConvertInfo)]) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    for (i, (first_line, first)) in entries.iter().enumerate() {
+        for (second_line, second) in &entries[i + 1..] {
+            let (a, b) = (first_line.number, second_line.number);
+            let lines = (a.min(b), a.max(b));
+
+            let sources = overlap(
+                first.source..first.source + first.range,
+                second.source..second.source + second.range,
+            );
+            if let Some(overlap) = sources {
+                issues.push(Issue::OverlappingSources {
+                    map: name.to_string(),
+                    lines,
+                    overlap,
+                });
+            }
+
+            let destinations = overlap(
+                first.destination..first.destination + first.range,
+                second.destination..second.destination + second.range,
+            );
+            if let Some(overlap) = destinations {
+                issues.push(Issue::OverlappingDestinations {
+                    map: name.to_string(),
+                    lines,
+                    overlap,
+                });
+            }
+        }
+    }
+
+    let mut covered_until: Option<u64> = None;
+    for (_, info) in entries {
+        if let Some(end) = covered_until {
+            if end < info.source {
+                issues.push(Issue::Gap {
+                    map: name.to_string(),
+                    gap: end..info.source,
+                });
+            }
+        }
+        let end = info.source + info.range;
+        covered_until = Some(covered_until.map_or(end, |covered| covered.max(end)));
+    }
+
+    issues
+}
+
+#[derive(Clone)]
+struct Converter {
+    /// The category converted from, like `seed` in `seed-to-soil map:`.
+    from: String,
+    /// The category converted to, like `soil` in `seed-to-soil map:`.
+    to: String,
+    /// Where the map's header is, to point errors at it.
+    header: Position,
+    values: Vec<ConvertInfo>,
+    /// Overlaps and gaps found while parsing.
+    issues: Vec<Issue>,
+}
+
+impl Converter {
+    fn new(block: &[Line]) -> Result<Converter, Error> {
+        let (header, data) = block.split_first().expect("blocks are never empty");
+        let (label, _) = header.split_once(header.text, ':')?;
+        let label = label.trim();
+        let (from, to) = label
+            .strip_suffix(" map")
+            .and_then(|name| name.split_once("-to-"))
+            .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+            .ok_or_else(|| header.unexpected(label, "'<source>-to-<destination> map'"))?;
+        let name = &format!("{}-to-{}", from, to);
+
+        let data = data.iter().map(|line| {
+            let numbers = line.numbers::<u64>(line.text)?;
+            if numbers.len() != 3 {
+                return Err(line.unexpected(line.text, "destination, source and range"));
+            }
+
+            let info = ConvertInfo {
+                destination: numbers[0],
+                source: numbers[1],
+                range: numbers[2],
+            };
+            // the ranges end right after their last value, which has to fit
+            for (start, kind) in [(info.destination, "destination"), (info.source, "source")] {
+                if start.checked_add(info.range).is_none() {
+                    return Err(Error::Overflow {
+                        position: line.position(line.text),
+                        quantity: format!("the end of the {} range", kind),
+                    });
+                }
+            }
+            Ok((*line, info))
+        });
+
+        //sort by source
+        let mut data = data.collect::<Result<Vec<_>, Error>>()?;
+        data.sort_by_key(|(_, a)| a.source);
+
+        let issues = validate(name, &data);
+
+        // overlapping sources make the map ambiguous, which is an error
+        let overlapping = issues.iter().find_map(|issue| match issue {
+            Issue::OverlappingSources { lines, .. } => Some(lines),
+            _ => None,
+        });
+        if let Some((first, second)) = overlapping {
+            let (line, _) = data
+                .iter()
+                .find(|(line, _)| line.number == *second)
+                .unwrap();
+            return Err(line.unexpected(
+                line.text,
+                format!("a source range not overlapping line {} of {}", first, name),
+            ));
+        }
+
+        Ok(Converter {
+            from: from.to_string(),
+            to: to.to_string(),
+            header: header.position(header.text),
+            values: data.into_iter().map(|(_, info)| info).collect(),
+            issues,
+        })
+    }
+
+    /// A map converting `category` to itself.
+    fn identity(category: &str) -> Converter {
+        Converter {
+            from: category.to_string(),
+            to: category.to_string(),
+            header: Position { line: 0, column: 0 },
+            values: vec![],
+            issues: vec![],
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("{}-to-{}", self.from, self.to)
+    }
+
+    fn convert(&self, value: u64) -> u64 {
+        let mut result = value;
+        for info in &self.values {
+            if value >= info.source && value < info.source + info.range {
+                result = info.destination + (value - info.source);
+            }
+        }
+        result
+    }
+
+    /// Maps `value` backwards, returning every source that converts to it
+    /// in ascending order. A destination can be hit by several sources, or
+    /// by none at all when it lies inside a range that's mapped elsewhere.
+    fn invert(&self, value: u64) -> Vec<u64> {
+        // unmapped values convert to themselves
+        let mut candidates = vec![value];
+        for info in &self.values {
+            if value >= info.destination && value < info.destination + info.range {
+                candidates.push(info.source + (value - info.destination));
+            }
+        }
+
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.retain(|source| self.convert(*source) == value);
+        candidates
+    }
+
+    /// Maps the half-open `range` through this converter. The range is cut
+    /// wherever it crosses the edge of a `ConvertInfo`, so every piece is
+    /// shifted by a single offset and can be converted by its start alone.
+    fn convert_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut cuts = vec![range.start, range.end];
+        for info in &self.values {
+            for edge in [info.source, info.source + info.range] {
+                if edge > range.start && edge < range.end {
+                    cuts.push(edge);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        cuts.windows(2)
+            .map(|cut| {
+                let start = self.convert(cut[0]);
+                start..start + (cut[1] - cut[0])
+            })
+            .collect()
+    }
+
+    /// Cuts the whole number line at every edge of a `ConvertInfo`, returning
+    /// each piece together with the value its start converts to.
+    fn pieces(&self) -> Vec<(Range<u64>, u64)> {
+        let mut cuts = vec![0, u64::MAX];
+        for info in &self.values {
+            cuts.push(info.source);
+            cuts.push(info.source + info.range);
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        cuts.windows(2)
+            .map(|cut| (cut[0]..cut[1], self.convert(cut[0])))
+            .collect()
+    }
+
+    /// Composes this converter with the `next` one into a single converter
+    /// doing both steps. The entries of the result are sorted by source and
+    /// never overlap; values converting to themselves get no entry.
+    fn compose(&self, next: &Converter) -> Converter {
+        let mut values: Vec<ConvertInfo> = vec![];
+
+        for (range, destination) in self.pieces() {
+            let mut source = range.start;
+            let converted =
+                next.convert_range(destination..destination + (range.end - range.start));
+
+            // the converted pieces come back in the order of their sources
+            for piece in converted {
+                let len = piece.end - piece.start;
+                push_segment(
+                    &mut values,
+                    ConvertInfo {
+                        destination: piece.start,
+                        source,
+                        range: len,
+                    },
+                );
+                source += len;
+            }
+        }
+
+        Converter {
+            from: self.from.clone(),
+            to: next.to.clone(),
+            header: self.header,
+            values,
+            issues: vec![],
+        }
+    }
+}
+
+/// Appends `info` to the sorted `values`, skipping identity segments and
+/// extending the last segment when `info` continues it with the same offset.
+fn push_segment(values: &mut Vec<ConvertInfo>, info: ConvertInfo) {
+    if info.range == 0 || info.destination == info.source {
+        return;
+    }
+
+    if let Some(last) = values.last_mut() {
+        if last.source + last.range == info.source
+            && last.destination + last.range == info.destination
+        {
+            last.range += info.range;
+            return;
+        }
+    }
+    values.push(info);
+}
+
+/// All converters of a pipeline composed into a single map from seed to
+/// location. The segments are sorted and never overlap, so a lookup is one
+/// binary search instead of a walk through every converter.
+pub struct PiecewiseMap {
+    name: String,
+    values: Vec<ConvertInfo>,
+}
+
+impl PiecewiseMap {
+    pub fn convert(&self, value: u64) -> u64 {
+        let index = self
+            .values
+            .partition_point(|info| info.source + info.range <= value);
+
+        match self.values.get(index) {
+            Some(info) if value >= info.source => info.destination + (value - info.source),
+            _ => value,
+        }
+    }
+}
+
+/// Prints the map in the almanac's own format, so it can be fed back in.
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} map:", self.name)?;
+        for info in &self.values {
+            writeln!(f, "{} {} {}", info.destination, info.source, info.range)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sorts `ranges` and joins the ones that overlap or touch, keeping the
+/// number of ranges from growing with every converter.
+fn merge_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<u64>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Maps `value` back through `converters`, last one first.
+fn invert_through(converters: &[Converter], value: u64) -> Vec<u64> {
+    let mut values = vec![value];
+    for converter in converters.iter().rev() {
+        values = values
+            .into_iter()
+            .flat_map(|value| converter.invert(value))
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+    }
+    values
+}
+
+/// Finds the shortest chain of maps leading from category `from` to `to`,
+/// returning the indices of the maps in order.
+fn find_route(maps: &[Converter], from: &str, to: &str) -> Option<Vec<usize>> {
+    // the map through which each category was first reached
+    let mut reached_by: HashMap<&str, usize> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::from([from]);
+    let mut queue: VecDeque<&str> = VecDeque::from([from]);
+
+    while let Some(category) = queue.pop_front() {
+        if category == to {
+            let mut route = vec![];
+            let mut category = to;
+            while let Some(index) = reached_by.get(category) {
+                route.push(*index);
+                category = &maps[*index].from;
+            }
+            route.reverse();
+            return Some(route);
+        }
+
+        for (index, map) in maps.iter().enumerate() {
+            if map.from == category && seen.insert(&map.to) {
+                reached_by.insert(&map.to, index);
+                queue.push_back(&map.to);
+            }
+        }
+    }
+    None
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    /// The category is on the path currently being walked.
+    Active,
+    Done,
+}
+
+/// Finds a map leading back to a category it can be reached from.
+fn find_cycle(maps: &[Converter]) -> Option<&Converter> {
+    fn visit<'a>(
+        maps: &'a [Converter],
+        category: &'a str,
+        visits: &mut HashMap<&'a str, Visit>,
+    ) -> Option<&'a Converter> {
+        visits.insert(category, Visit::Active);
+        for map in maps.iter().filter(|map| map.from == category) {
+            match visits.get(map.to.as_str()) {
+                Some(Visit::Active) => return Some(map),
+                Some(Visit::Done) => {}
+                None => {
+                    if let Some(map) = visit(maps, &map.to, visits) {
+                        return Some(map);
+                    }
+                }
+            }
+        }
+        visits.insert(category, Visit::Done);
+        None
+    }
+
+    let mut visits = HashMap::new();
+    for map in maps {
+        if !visits.contains_key(map.from.as_str()) {
+            if let Some(map) = visit(maps, &map.from, &mut visits) {
+                return Some(map);
+            }
+        }
+    }
+    None
+}
+
+/// A chain of maps converting one category into another.
+pub struct ProductionPipeline {
+    from: String,
+    to: String,
+    converters: Vec<Converter>,
+}
+
+impl ProductionPipeline {
+    /// Resolves the chain of `maps` from category `from` to `to`, whatever
+    /// order the maps are listed in. `end` is where the error for a missing
+    /// link points.
+    fn between(
+        maps: &[Converter],
+        from: &str,
+        to: &str,
+        end: Position,
+    ) -> Result<ProductionPipeline, Error> {
+        let route = find_route(maps, from, to).ok_or_else(|| Error::MissingSection {
+            position: end,
+            section: format!("a chain of maps from '{}' to '{}'", from, to),
+        })?;
+
+        Ok(ProductionPipeline {
+            from: from.to_string(),
+            to: to.to_string(),
+            converters: route.into_iter().map(|index| maps[index].clone()).collect(),
+        })
+    }
+
+    pub fn convert(&self, value: u64) -> u64 {
+        let mut result = value;
+        for converter in &self.converters {
+            result = converter.convert(result);
+        }
+        result
+    }
+
+    /// Composes every converter into one piecewise map.
+    fn flatten(&self) -> PiecewiseMap {
+        let identity = Converter::identity(&self.from);
+        let composed = self
+            .converters
+            .iter()
+            .fold(identity, |composed, converter| composed.compose(converter));
+
+        PiecewiseMap {
+            name: format!("{}-to-{}", self.from, self.to),
+            values: composed.values,
+        }
+    }
+
+    /// Maps a value back through every converter, returning all values of
+    /// the first category that end up there in ascending order.
+    pub fn invert(&self, value: u64) -> Vec<u64> {
+        invert_through(&self.converters, value)
+    }
+
+    /// Maps the half-open `range` through every converter, returning the
+    /// sorted, non-overlapping ranges they end up in.
+    pub fn convert_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut ranges = vec![range];
+        for converter in &self.converters {
+            let converted = ranges
+                .into_iter()
+                .flat_map(|range| converter.convert_range(range))
+                .collect();
+            ranges = merge_ranges(converted);
+        }
+        ranges
+    }
+}
+
+/**
+* NOTE: Puzzle
+*/
+pub struct Almanac {
+    seeds: Vec<u64>,
+    /// Every map, in the order they are listed.
+    maps: Vec<Converter>,
+    /// The maps chained from `seed` to `location`.
+    production: ProductionPipeline,
+    /// Position right behind the input.
+    end: Position,
+}
+
+impl Almanac {
+    /// Overlapping destinations and gaps found in the maps. Overlapping
+    /// sources are rejected by the parser instead.
+    pub fn warnings(&self) -> Vec<&Issue> {
+        self.maps
+            .iter()
+            .flat_map(|converter| &converter.issues)
+            .collect()
+    }
+
+    /// Chains the maps needed to convert category `from` into `to`, e.g.
+    /// `soil` into `humidity`.
+    pub fn pipeline(&self, from: &str, to: &str) -> Result<ProductionPipeline, Error> {
+        ProductionPipeline::between(&self.maps, from, to, self.end)
+    }
+
+    /// The whole pipeline as a single seed-to-location map.
+    pub fn flatten(&self) -> PiecewiseMap {
+        self.production.flatten()
+    }
+
+    /// Every seed, listed or not, that ends up at `location`.
+    pub fn seeds_for(&self, location: u64) -> Vec<u64> {
+        self.production.invert(location)
+    }
+
+    /// Alternative to the range based part two, built on the inverse mapping.
+    ///
+    /// Within a seed range the location only jumps where some converter's
+    /// input crosses the edge of a `ConvertInfo`, so the lowest location is
+    /// reached at a range start or at one of those edges pulled back to a
+    /// seed. Checking those few candidate seeds is enough.
+    pub fn lowest_location_by_inversion(&self) -> u64 {
+        let converters = &self.production.converters;
+        let mut candidates: Vec<u64> = self.seeds.chunks(2).map(|seeds| seeds[0]).collect();
+
+        for (index, converter) in converters.iter().enumerate() {
+            for info in &converter.values {
+                for edge in [info.source, info.source + info.range] {
+                    candidates.extend(invert_through(&converters[..index], edge));
+                }
+            }
+        }
+
+        candidates
+            .into_iter()
+            .filter(|seed| {
+                self.seeds
+                    .chunks(2)
+                    .any(|seeds| *seed >= seeds[0] && *seed < seeds[0] + seeds[1])
+            })
+            .map(|seed| self.production.convert(seed))
+            .min()
+            .unwrap_or(u64::MAX)
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Almanac;
+    type Answer = u64;
+
+    fn parse(&self, data: &str) -> Result<Almanac, Error> {
+        let blocks = aoc::blocks(data);
+        let (seeds, maps) = blocks.split_first().ok_or(Error::MissingSection {
+            position: aoc::end(data),
+            section: "seeds".to_string(),
+        })?;
+
+        let seeds = get_seeds(&seeds[0])?;
+        let maps = maps
+            .iter()
+            .map(|block| Converter::new(block))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for (index, map) in maps.iter().enumerate() {
+            if maps[..index].iter().any(|other| other.name() == map.name()) {
+                return Err(Error::UnexpectedToken {
+                    position: map.header,
+                    expected: format!("a single {} map", map.name()),
+                    found: map.name(),
+                });
+            }
+        }
+        if let Some(map) = find_cycle(&maps) {
+            return Err(Error::UnexpectedToken {
+                position: map.header,
+                expected: format!("a map not leading back to '{}'", map.to),
+                found: map.name(),
+            });
+        }
+
+        let end = aoc::end(data);
+        let production = ProductionPipeline::between(&maps, "seed", "location", end)?;
+
+        Ok(Almanac {
+            seeds,
+            maps,
+            production,
+            end,
+        })
+    }
+
+    fn part_one(&self, almanac: &Almanac) -> Result<u64, Error> {
+        let production = almanac.flatten();
+        let mut lowest_converted = u64::MAX;
+
+        almanac.seeds.iter().for_each(|seed| {
+            let result = production.convert(*seed);
+            if result < lowest_converted {
+                lowest_converted = result;
+            }
+        });
+        Ok(lowest_converted)
+    }
+
+    fn part_two(&self, almanac: &Almanac) -> Result<u64, Error> {
+        let mut lowest_converted = u64::MAX;
+
+        // the seeds come in pairs of start and length
+        for seeds in almanac.seeds.chunks(2) {
+            let ranges = almanac
+                .production
+                .convert_range(seeds[0]..seeds[0] + seeds[1]);
+
+            if let Some(range) = ranges.first() {
+                lowest_converted = lowest_converted.min(range.start);
+            }
+        }
+
+        Ok(lowest_converted)
+    }
+}
+
+fn get_seeds(line: &Line) -> Result<Vec<u64>, Error> {
+    let (label, seeds) = line.split_once(line.text, ':')?;
+    line.expect(label, "seeds")?;
+
+    let tokens = seeds.split_whitespace().collect::<Vec<_>>();
+    let seeds = line.numbers::<u64>(seeds)?;
+    // part two reads the seeds as pairs of start and length
+    if seeds.len() % 2 == 1 {
+        return Err(line.missing(line.text, "length of the last seed range"));
+    }
+    for (index, pair) in seeds.chunks(2).enumerate() {
+        if pair[0].checked_add(pair[1]).is_none() {
+            return Err(Error::Overflow {
+                position: line.position(tokens[2 * index]),
+                quantity: "the end of the seed range".to_string(),
+            });
+        }
+    }
+    Ok(seeds)
+}
+
+/**
+* NOTE: Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DATA: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_converter() {
+        let data = "seed-to-soil map:
+50 98 2
+52 50 48
+";
+
+        let lines = aoc::lines(data).collect::<Vec<_>>();
+        let converter = Converter::new(&lines).unwrap();
+
+        assert_eq!(converter.values.len(), 2);
+
+        assert_eq!(converter.values[1].destination, 50);
+        assert_eq!(converter.values[1].source, 98);
+        assert_eq!(converter.values[1].range, 2);
+
+        assert_eq!(converter.values[0].destination, 52);
+        assert_eq!(converter.values[0].source, 50);
+        assert_eq!(converter.values[0].range, 48);
+
+        assert_eq!(converter.convert(79), 81);
+        assert_eq!(converter.convert(98), 50);
+        assert_eq!(converter.convert(14), 14);
+        assert_eq!(converter.convert(55), 57);
+        assert_eq!(converter.convert(13), 13);
+    }
+
+    #[test]
+    fn test_converter_range() {
+        let data = "seed-to-soil map:
+50 98 2
+52 50 48
+";
+
+        let lines = aoc::lines(data).collect::<Vec<_>>();
+        let converter = Converter::new(&lines).unwrap();
+
+        assert_eq!(converter.convert_range(79..93), vec![81..95]);
+        assert_eq!(converter.convert_range(40..60), vec![40..50, 52..62]);
+        assert_eq!(
+            converter.convert_range(95..105),
+            vec![97..100, 50..52, 100..105]
+        );
+        assert_eq!(converter.convert_range(10..10), vec![]);
+    }
+
+    #[test]
+    fn test_pipeline_range_matches_single_seeds() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+        let production = &almanac.production;
+
+        for start in 0..100 {
+            for len in [1, 7, 30] {
+                let ranges = production.convert_range(start..start + len);
+                let mut expected = (start..start + len)
+                    .map(|seed| production.convert(seed))
+                    .collect::<Vec<_>>();
+                expected.sort_unstable();
+
+                let converted = ranges.into_iter().flatten().collect::<Vec<_>>();
+                assert_eq!(converted, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_converter_invert() {
+        let data = "seed-to-soil map:
+50 98 2
+52 50 48
+";
+
+        let lines = aoc::lines(data).collect::<Vec<_>>();
+        let converter = Converter::new(&lines).unwrap();
+
+        assert_eq!(converter.invert(81), vec![79]);
+        assert_eq!(converter.invert(14), vec![14]);
+        assert_eq!(converter.invert(50), vec![98]);
+
+        let data = "light-to-temperature map:
+0 10 5
+";
+
+        let lines = aoc::lines(data).collect::<Vec<_>>();
+        let converter = Converter::new(&lines).unwrap();
+
+        // 3 is reached from 13 as well as from itself
+        assert_eq!(converter.invert(3), vec![3, 13]);
+        // 12 is mapped elsewhere and nothing maps onto it
+        assert_eq!(converter.invert(12), vec![]);
+    }
+
+    #[test]
+    fn test_seeds_for() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+
+        assert_eq!(almanac.seeds_for(82), vec![79]);
+        assert_eq!(almanac.seeds_for(35), vec![13]);
+
+        for location in 0..120 {
+            for seed in almanac.seeds_for(location) {
+                assert_eq!(almanac.production.convert(seed), location);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lowest_location_by_inversion() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+
+        assert_eq!(
+            almanac.lowest_location_by_inversion(),
+            Day5.part_two(&almanac).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = "seed-to-soil map:
+50 98 2
+52 50 48
+";
+        let second = "soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+";
+
+        let first = Converter::new(&aoc::lines(first).collect::<Vec<_>>()).unwrap();
+        let second = Converter::new(&aoc::lines(second).collect::<Vec<_>>()).unwrap();
+        let composed = first.compose(&second);
+
+        for value in 0..120 {
+            assert_eq!(
+                composed.convert(value),
+                second.convert(first.convert(value))
+            );
+        }
+        for pair in composed.values.windows(2) {
+            assert!(pair[0].source + pair[0].range <= pair[1].source);
+        }
+    }
+
+    #[test]
+    fn test_flatten() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+        let flat = almanac.flatten();
+
+        for seed in 0..200 {
+            assert_eq!(flat.convert(seed), almanac.production.convert(seed));
+        }
+
+        // the printed map parses back into the same conversion
+        let printed = flat.to_string();
+        assert!(printed.starts_with("seed-to-location map:\n"));
+        let lines = aoc::lines(&printed).collect::<Vec<_>>();
+        let converter = Converter::new(&lines).unwrap();
+        assert_eq!(converter.values, flat.values);
+    }
+
+    #[test]
+    fn test_validation() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+        assert_eq!(almanac.warnings(), Vec::<&Issue>::new());
+
+        let data = "seed-to-soil map:
+50 98 2
+52 50 48
+";
+        let converter = Converter::new(&aoc::lines(data).collect::<Vec<_>>()).unwrap();
+        assert_eq!(converter.name(), "seed-to-soil");
+        assert_eq!(converter.issues, vec![]);
+
+        let data = "soil-to-fertilizer map:
+0 15 37
+30 60 10
+45 0 10
+";
+        let converter = Converter::new(&aoc::lines(data).collect::<Vec<_>>()).unwrap();
+        let issues = converter
+            .issues
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            vec![
+                "soil-to-fertilizer: lines 2 and 3 overlap in destination range 30..37",
+                "soil-to-fertilizer: source range 10..15 is not mapped",
+                "soil-to-fertilizer: source range 52..60 is not mapped",
+            ]
+        );
+
+        let data = "water-to-light map:
+88 18 7
+18 20 70
+";
+        let result = Converter::new(&aoc::lines(data).collect::<Vec<_>>());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 3, col 1: expected a source range not overlapping line 2 of water-to-light, got '18 20 70'"
+        );
+
+        // a range ending past `u64::MAX` is an error, one ending on it isn't
+        let data = "light-to-temperature map:
+0 18446744073709551615 5
+";
+        let result = Converter::new(&aoc::lines(data).collect::<Vec<_>>());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 2, col 1: the end of the source range is too large"
+        );
+        let data = "light-to-temperature map:
+18446744073709551610 0 6
+";
+        let result = Converter::new(&aoc::lines(data).collect::<Vec<_>>());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 2, col 1: the end of the destination range is too large"
+        );
+        let data = "light-to-temperature map:
+18446744073709551610 0 5
+";
+        assert!(Converter::new(&aoc::lines(data).collect::<Vec<_>>()).is_ok());
+    }
+
+    #[test]
+    fn test_maps_out_of_order() {
+        let mut blocks = TEST_DATA.split("\n\n").collect::<Vec<_>>();
+        let seeds = blocks.remove(0);
+        blocks.reverse();
+        blocks.swap(1, 4);
+        let data = format!("{}\n\n{}", seeds, blocks.join("\n\n"));
+
+        let almanac = Day5.parse(&data).unwrap();
+        assert_eq!(Day5.part_one(&almanac).unwrap(), 35);
+        assert_eq!(Day5.part_two(&almanac).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_pipeline_between_categories() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+
+        // seed 79 is soil 81, fertilizer 81, water 81, light 74, temperature 78 and humidity 78
+        let pipeline = almanac.pipeline("soil", "humidity").unwrap();
+        assert_eq!(pipeline.convert(81), 78);
+        assert_eq!(pipeline.converters.len(), 5);
+
+        let pipeline = almanac.pipeline("light", "light").unwrap();
+        assert_eq!(pipeline.convert(74), 74);
+
+        let result = almanac.pipeline("humidity", "soil");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 34, col 1: missing a chain of maps from 'humidity' to 'soil'"
+        );
+    }
+
+    #[test]
+    fn test_broken_chains() {
+        let data = TEST_DATA.replace("water-to-light", "water-to-lamp");
+        let result = Day5.parse(&data);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 34, col 1: missing a chain of maps from 'seed' to 'location'"
+        );
+
+        let data = format!("{}\n\nlocation-to-soil map:\n1 2 3", TEST_DATA);
+        let result = Day5.parse(&data);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 35, col 1: expected a map not leading back to 'soil', got 'location-to-soil'"
+        );
+
+        let data = format!("{}\n\nseed-to-soil map:\n1 2 3", TEST_DATA);
+        let result = Day5.parse(&data);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 35, col 1: expected a single seed-to-soil map, got 'seed-to-soil'"
+        );
+
+        let result = Day5.parse("seeds: 1 2\n\nseed-soil map:\n1 2 3");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 3, col 1: expected '<source>-to-<destination> map', got 'seed-soil map'"
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+        let result = Day5.part_one(&almanac).unwrap();
+
+        assert_eq!(result, 35);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let almanac = Day5.parse(TEST_DATA).unwrap();
+        let result = Day5.part_two(&almanac).unwrap();
+
+        assert_eq!(result, 46);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let result = Day5.parse("");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 1, col 1: missing seeds"
+        );
+
+        let result = Day5.parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 5O 48");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 5, col 4: expected number, got '5O'"
+        );
+
+        let result = Day5.parse("seeds: 79 14\n\nseed-to-soil map:\n50 98");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 4, col 1: expected destination, source and range, got '50 98'"
+        );
+
+        let result =
+            Day5.parse("seeds: 79 14 18446744073709551610 100\n\nseed-to-soil map:\n50 98 2");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 1, col 14: the end of the seed range is too large"
+        );
+    }
+}
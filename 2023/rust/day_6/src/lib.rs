@@ -0,0 +1,502 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc::{Error, Line, Solution};
+use num_bigint::BigUint;
+use num_traits::{CheckedMul, CheckedSub, ToPrimitive, Unsigned};
+
+/// An unsigned integer type races can be measured in.
+pub trait Measure:
+    Clone + Ord + fmt::Debug + fmt::Display + FromStr + Unsigned + CheckedMul + CheckedSub
+{
+    /// The largest value whose square is at most `self`.
+    fn isqrt(&self) -> Self;
+}
+
+impl Measure for u64 {
+    fn isqrt(&self) -> u64 {
+        u64::isqrt(*self)
+    }
+}
+
+impl Measure for u128 {
+    fn isqrt(&self) -> u128 {
+        u128::isqrt(*self)
+    }
+}
+
+impl Measure for BigUint {
+    fn isqrt(&self) -> BigUint {
+        self.sqrt()
+    }
+}
+
+/// A calculation didn't fit in the integer type of the race.
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "number too large for the integer type")
+    }
+}
+
+#[derive(Debug)]
+pub struct Race<T> {
+    time: T,
+    distance: T,
+}
+
+impl<T: Measure> Race<T> {
+    fn new(time: T, distance: T) -> Race<T> {
+        Race { time, distance }
+    }
+
+    fn beats_record(&self, hold_time: &T) -> bool {
+        let travel_time = self.time.clone() - hold_time.clone();
+        // a distance too large for `T` beats any record that fits
+        travel_time
+            .checked_mul(hold_time)
+            .is_none_or(|distance| distance > self.distance)
+    }
+
+    /// The hold times beating the record, if any.
+    ///
+    /// Holding for `h` travels `h * (time - h)`, so the winning hold times lie
+    /// strictly between the roots of `h² - time·h + distance`. The integer
+    /// square root only approximates the lower root, so it's nudged onto the
+    /// first winning hold time; the last one mirrors it around `time / 2`.
+    pub fn winning_holds(&self) -> Result<Option<RangeInclusive<T>>, Overflow> {
+        let two = T::one() + T::one();
+        let square = self.time.checked_mul(&self.time).ok_or(Overflow)?;
+        let record = self
+            .distance
+            .checked_mul(&(two.clone() * two.clone()))
+            .ok_or(Overflow)?;
+        let Some(discriminant) = square.checked_sub(&record) else {
+            return Ok(None);
+        };
+
+        let half = self.time.clone() / two.clone();
+        let mut first = (self.time.clone() - discriminant.isqrt()) / two;
+        while !first.is_zero() && self.beats_record(&(first.clone() - T::one())) {
+            first = first - T::one();
+        }
+        while first <= half && !self.beats_record(&first) {
+            first = first + T::one();
+        }
+
+        Ok((first <= half).then(|| first.clone()..=self.time.clone() - first))
+    }
+
+    /// How many hold times beat the record.
+    pub fn num_of_wins(&self) -> Result<T, Overflow> {
+        let holds = self.winning_holds()?;
+        Ok(holds.map_or(T::zero(), |holds| {
+            holds.end().clone() - holds.start().clone() + T::one()
+        }))
+    }
+}
+
+/// Exploring how far each hold time gets, to see where the wins come from.
+impl<T: Measure + From<u32> + ToPrimitive> Race<T> {
+    pub fn time(&self) -> &T {
+        &self.time
+    }
+
+    pub fn record(&self) -> &T {
+        &self.distance
+    }
+
+    /// The hold time travelling furthest. Holding for `time - optimal` goes
+    /// just as far.
+    pub fn optimal_hold(&self) -> T {
+        self.time.clone() / T::from(2)
+    }
+
+    pub fn max_distance(&self) -> Result<T, Overflow> {
+        let hold = self.optimal_hold();
+        (self.time.clone() - hold.clone())
+            .checked_mul(&hold)
+            .ok_or(Overflow)
+    }
+
+    /// Every hold time from `0` to `time` along with how far it travels.
+    pub fn distances(&self) -> Result<Vec<(T, T)>, Overflow> {
+        let mut distances = vec![];
+        let mut hold = T::zero();
+        while hold <= self.time {
+            let travel_time = self.time.clone() - hold.clone();
+            let distance = travel_time.checked_mul(&hold).ok_or(Overflow)?;
+            distances.push((hold.clone(), distance));
+            hold = hold + T::one();
+        }
+        Ok(distances)
+    }
+
+    /// The distance curve as CSV, one row per hold time.
+    pub fn to_csv(&self) -> Result<String, Overflow> {
+        let mut csv = String::from("hold,distance,beats_record\n");
+        for (hold, distance) in self.distances()? {
+            let wins = distance > self.distance;
+            csv += &format!("{},{},{}\n", hold, distance, wins);
+        }
+        Ok(csv)
+    }
+
+    /// The distance curve as a bar per hold time, scaled so the longest
+    /// distance is `width` wide. The record is marked by a `|` column, and
+    /// winning hold times by a `*`.
+    pub fn chart(&self, width: u32) -> Result<String, Overflow> {
+        let distances = self.distances()?;
+        let max_distance = self.max_distance()?.max(self.distance.clone());
+        let hold_width = self.time.to_string().len();
+        let distance_width = max_distance.to_string().len();
+
+        // the column a distance reaches, rounded down
+        let column = |distance: &T| -> Result<usize, Overflow> {
+            if max_distance.is_zero() {
+                return Ok(0);
+            }
+            let scaled = distance.checked_mul(&T::from(width)).ok_or(Overflow)?;
+            Ok((scaled / max_distance.clone())
+                .to_usize()
+                .unwrap_or(usize::MAX))
+        };
+        let record = column(&self.distance)?;
+
+        // label the record column, right above the bars
+        let indent = hold_width + 1 + distance_width + 2 + record;
+        let mut chart = format!("{:indent$}v record {}\n", "", self.distance);
+        for (hold, distance) in distances {
+            let bar = column(&distance)?;
+            let mut row = String::new();
+            for x in 0..=(width as usize).max(record) {
+                row.push(match x {
+                    _ if x == record => '|',
+                    _ if x < bar => '#',
+                    _ => ' ',
+                });
+            }
+            let wins = if distance > self.distance { " *" } else { "" };
+            chart += &format!(
+                "{:>hold_width$} {:>distance_width$}  {}{}\n",
+                hold,
+                distance,
+                row.trim_end(),
+                wins
+            );
+        }
+        Ok(chart)
+    }
+}
+
+#[derive(Debug)]
+struct Track<T> {
+    races: Vec<Race<T>>,
+}
+
+impl<T: Measure> Track<T> {
+    fn new(data: &str) -> Result<Track<T>, Error> {
+        let mut lines = aoc::lines(data);
+        let times_line = lines.next();
+        let times = Track::read_row(data, times_line, "Time")?;
+
+        let distances_line = lines.next();
+        let distances = Track::read_row(data, distances_line, "Distance")?;
+
+        if times.len() != distances.len() {
+            let line = distances_line.unwrap();
+            return Err(Error::UnexpectedToken {
+                position: line.position(line.text),
+                expected: format!("{} distances", times.len()),
+                found: format!("{} distances", distances.len()),
+            });
+        }
+
+        let mut races = vec![];
+        for (time, distance) in times.into_iter().zip(distances) {
+            races.push(Race::new(time, distance));
+        }
+
+        Ok(Track { races })
+    }
+
+    /// Parses a `label: 1 2 3` row of the sheet.
+    fn read_row(data: &str, line: Option<Line>, label: &str) -> Result<Vec<T>, Error> {
+        let line = line.ok_or_else(|| Error::MissingSection {
+            position: aoc::end(data),
+            section: format!("'{}' row", label),
+        })?;
+
+        let (found, numbers) = line.split_once(line.text, ':')?;
+        line.expect(found, label)?;
+
+        let numbers = line.numbers::<T>(numbers)?;
+        if numbers.is_empty() {
+            return Err(line.missing(line.text, format!("{} values", label.to_lowercase())));
+        }
+        Ok(numbers)
+    }
+
+    fn get_num_of_wins(&self) -> Result<Vec<T>, Overflow> {
+        self.races.iter().map(|x| x.num_of_wins()).collect()
+    }
+
+    fn get_total_num_of_wins(&self) -> Result<T, Overflow> {
+        self.get_num_of_wins()?
+            .into_iter()
+            .try_fold(T::one(), |a, b| a.checked_mul(&b).ok_or(Overflow))
+    }
+
+    /// The same races measured in a wider integer type.
+    fn widen<U: Measure + From<T>>(&self) -> Track<U> {
+        let races = self
+            .races
+            .iter()
+            .map(|race| Race::new(race.time.clone().into(), race.distance.clone().into()))
+            .collect();
+        Track { races }
+    }
+}
+
+/**
+* NOTE: Puzzle
+*/
+pub struct Sheet {
+    /// The races as listed on the sheet.
+    track: Track<u64>,
+    /// A single race read with the spaces between the numbers ignored, which
+    /// can be arbitrarily long.
+    kerned_track: Track<BigUint>,
+}
+
+impl Sheet {
+    /// The races as listed on the sheet.
+    pub fn races(&self) -> &[Race<u64>] {
+        &self.track.races
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Sheet;
+    type Answer = BigUint;
+
+    fn parse(&self, data: &str) -> Result<Sheet, Error> {
+        let track = Track::new(data)?;
+        let kerned_track = Track::new(&data.replace(' ', ""))?;
+
+        Ok(Sheet {
+            track,
+            kerned_track,
+        })
+    }
+
+    fn part_one(&self, sheet: &Sheet) -> Result<BigUint, Error> {
+        Ok(match sheet.track.get_total_num_of_wins() {
+            Ok(total) => total.into(),
+            Err(Overflow) => sheet
+                .track
+                .widen::<BigUint>()
+                .get_total_num_of_wins()
+                .expect("big integers don't overflow"),
+        })
+    }
+
+    fn part_two(&self, sheet: &Sheet) -> Result<BigUint, Error> {
+        Ok(sheet
+            .kerned_track
+            .get_total_num_of_wins()
+            .expect("big integers don't overflow"))
+    }
+}
+
+/**
+* NOTE: Tests
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DATA: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    fn brute_force_wins(race: &Race<u64>) -> Option<RangeInclusive<u64>> {
+        let mut wins = (0..=race.time).filter(|hold_time| race.beats_record(hold_time));
+        let first = wins.next()?;
+        Some(first..=wins.next_back().unwrap_or(first))
+    }
+
+    #[test]
+    fn test_winning_holds() {
+        assert_eq!(Race::new(7u64, 9).winning_holds(), Ok(Some(2..=5)));
+        assert_eq!(Race::new(15u64, 40).winning_holds(), Ok(Some(4..=11)));
+        assert_eq!(Race::new(30u64, 200).winning_holds(), Ok(Some(11..=19)));
+        assert_eq!(Race::new(71530u64, 940200).num_of_wins(), Ok(71503));
+
+        // the best hold only ties the record
+        assert_eq!(Race::new(6u64, 9).winning_holds(), Ok(None));
+        assert_eq!(Race::new(6u64, 8).winning_holds(), Ok(Some(3..=3)));
+        assert_eq!(Race::new(0u64, 0).winning_holds(), Ok(None));
+        assert_eq!(Race::new(1u64, 0).winning_holds(), Ok(None));
+        assert_eq!(Race::new(2u64, 0).winning_holds(), Ok(Some(1..=1)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = u64::MAX;
+        assert_eq!(Race::new(max, max).winning_holds(), Err(Overflow));
+        assert_eq!(Race::new(1u64 << 32, 0).num_of_wins(), Err(Overflow));
+
+        let race = Race::new(max as u128, max as u128);
+        assert_eq!(race.winning_holds(), Ok(Some(2..=max as u128 - 2)));
+
+        let track = Track::<u64>::new("Time: 18446744073709551615 3\nDistance: 0 0").unwrap();
+        assert_eq!(track.get_total_num_of_wins(), Err(Overflow));
+        let track = track.widen::<u128>();
+        assert_eq!(track.get_total_num_of_wins(), Ok(2 * (max as u128 - 1)));
+    }
+
+    #[test]
+    fn test_big_integers() {
+        let data = "Time: 7153071530 7153071530 7153071530
+Distance: 940200940200940200 940200940200940200 940200940200940200";
+        let sheet = Day6.parse(data).unwrap();
+
+        // squaring the times overflows u64, so part one falls back to big integers
+        assert_eq!(sheet.track.get_total_num_of_wins(), Err(Overflow));
+        let wins = BigUint::from(7153071530u64 - 2 * 133948492 + 1);
+        assert_eq!(Day6.part_one(&sheet).unwrap(), wins.pow(3));
+
+        let wins = BigUint::from_str("715304524263294080474608796043").unwrap();
+        assert_eq!(Day6.part_two(&sheet).unwrap(), wins);
+
+        let track = Track::<u64>::new(&data.replace(' ', ""));
+        assert_eq!(
+            track.err().unwrap().to_string(),
+            "line 1, col 6: expected number, got '715307153071530715307153071530'"
+        );
+    }
+
+    #[test]
+    fn test_winning_holds_match_brute_force() {
+        // xorshift, so the races are random but reproducible
+        let mut state: u64 = 0x2023_0006;
+        let mut random = |limit: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % limit
+        };
+
+        for _ in 0..10_000 {
+            let time = random(500);
+            let best = (time / 2) * (time - time / 2);
+            let distance = random(best + 10);
+            let race = Race::new(time, distance);
+
+            let expected = brute_force_wins(&race);
+            assert_eq!(race.winning_holds(), Ok(expected.clone()), "{:?}", race);
+
+            // wider types give the same hold times
+            let wide = Race::new(BigUint::from(time), BigUint::from(distance));
+            let expected = expected.map(|holds| (*holds.start()).into()..=(*holds.end()).into());
+            assert_eq!(wide.winning_holds(), Ok(expected), "{:?}", race);
+        }
+    }
+
+    #[test]
+    fn test_strategy() {
+        let race = Race::new(7u64, 9);
+        assert_eq!(race.optimal_hold(), 3);
+        assert_eq!(race.max_distance(), Ok(12));
+
+        let distances = race.distances().unwrap();
+        let expected = [
+            (0, 0),
+            (1, 6),
+            (2, 10),
+            (3, 12),
+            (4, 12),
+            (5, 10),
+            (6, 6),
+            (7, 0),
+        ];
+        assert_eq!(distances, expected);
+
+        let csv = race.to_csv().unwrap();
+        assert!(csv.starts_with("hold,distance,beats_record\n0,0,false\n1,6,false\n2,10,true\n"));
+        assert_eq!(csv.lines().count(), 9);
+
+        let max = u64::MAX;
+        assert_eq!(Race::new(max, 0).max_distance(), Err(Overflow));
+        let race = Race::new(max as u128, 0);
+        assert_eq!(
+            race.max_distance(),
+            Ok((max / 2) as u128 * (max / 2 + 1) as u128)
+        );
+    }
+
+    #[test]
+    fn test_chart() {
+        let chart = Race::new(7u64, 9).chart(12).unwrap();
+        let expected = "               v record 9
+0  0           |
+1  6  ######   |
+2 10  #########| *
+3 12  #########|## *
+4 12  #########|## *
+5 10  #########| *
+6  6  ######   |
+7  0           |
+";
+        assert_eq!(chart, expected);
+
+        // a record no hold time reaches is still marked
+        let chart = Race::new(2u64, 3).chart(4).unwrap();
+        let expected = "         v record 3
+0 0      |
+1 1  #   |
+2 0      |
+";
+        assert_eq!(chart, expected);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let sheet = Day6.parse(TEST_DATA).unwrap();
+        let result = Day6.part_one(&sheet).unwrap();
+        assert_eq!(result, 288u64.into());
+    }
+
+    #[test]
+    fn test_part_two() {
+        let sheet = Day6.parse(TEST_DATA).unwrap();
+        let result = Day6.part_two(&sheet).unwrap();
+        assert_eq!(result, 71503u64.into());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let result = Day6.parse("Time:      7  15   30");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 2, col 1: missing 'Distance' row"
+        );
+
+        let result = Day6.parse("Time:      7  15   30\nDistance:  9  4O  200");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 2, col 15: expected number, got '4O'"
+        );
+
+        let result = Day6.parse("Time:      7  15   30\nDistance:  9  40");
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "line 2, col 1: expected 3 distances, got '2 distances'"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::registry::Part;
+
+/// The accepted answers of a day's real input, as stored in its
+/// `answers.txt`:
+///
+/// ```text
+/// part one: 55090
+/// part two: 54845
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+/// Outcome of comparing an answer against the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(data: &str) -> Result<Answers, Error> {
+        let mut answers = Answers::default();
+
+        for line in crate::input::lines(data) {
+            let (label, answer) = line.split_once(line.text, ':')?;
+            let part = match label.trim() {
+                "part one" => Part::One,
+                "part two" => Part::Two,
+                _ => return Err(line.unexpected(label.trim(), "'part one' or 'part two'")),
+            };
+
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Err(line.missing(line.text, "answer"));
+            }
+            answers.set(part, answer);
+        }
+
+        Ok(answers)
+    }
+
+    /// Loads the answers at `path`; a missing file means nothing is recorded yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, Error> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(data) => Answers::parse(&data),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(source) => Err(Error::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    pub fn set<S: Into<String>>(&mut self, part: Part, answer: S) {
+        let answer = Some(answer.into());
+        match part {
+            Part::One => self.part_one = answer,
+            Part::Two => self.part_two = answer,
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(f, "part one: {}", answer)?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(f, "part two: {}", answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let answers = Answers::parse("part one: 35\n").unwrap();
+
+        assert_eq!(answers.check(Part::One, "35"), Verdict::Pass);
+        assert_eq!(
+            answers.check(Part::One, "36"),
+            Verdict::Fail {
+                expected: "35".to_string()
+            }
+        );
+        assert_eq!(answers.check(Part::Two, "46"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.set(Part::Two, "46");
+        answers.set(Part::One, "35");
+
+        let data = answers.to_string();
+        assert_eq!(data, "part one: 35\npart two: 46\n");
+        assert_eq!(Answers::parse(&data).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let result = Answers::parse("part one: 35\npart three: 46");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 1: expected 'part one' or 'part two', got 'part three'"
+        );
+    }
+}
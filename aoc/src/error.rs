@@ -0,0 +1,105 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// A 1-based location in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, col {}", self.line, self.column)
+    }
+}
+
+/// Everything that can go wrong while reading or parsing a puzzle input.
+#[derive(Debug)]
+pub enum Error {
+    /// The input file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// A token other than the expected one was found.
+    UnexpectedToken {
+        position: Position,
+        expected: String,
+        found: String,
+    },
+    /// A required part of the input, like a separator or a whole line, is absent.
+    MissingSection { position: Position, section: String },
+    /// A token that should have been a number could not be parsed as one.
+    InvalidNumber { position: Position, found: String },
+    /// A quantity derived from the input doesn't fit the integer type
+    /// counting it.
+    Overflow {
+        position: Position,
+        quantity: String,
+    },
+}
+
+impl Error {
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Error::Io { .. } => None,
+            Error::UnexpectedToken { position, .. }
+            | Error::MissingSection { position, .. }
+            | Error::InvalidNumber { position, .. }
+            | Error::Overflow { position, .. } => Some(*position),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "unable to read {}: {}", path.display(), source)
+            }
+            Error::UnexpectedToken {
+                position,
+                expected,
+                found,
+            } => write!(f, "{}: expected {}, got '{}'", position, expected, found),
+            Error::MissingSection { position, section } => {
+                write!(f, "{}: missing {}", position, section)
+            }
+            Error::InvalidNumber { position, found } => {
+                write!(f, "{}: expected number, got '{}'", position, found)
+            }
+            Error::Overflow { position, quantity } => {
+                write!(f, "{}: {} is too large", position, quantity)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = Error::InvalidNumber {
+            position: Position {
+                line: 17,
+                column: 9,
+            },
+            found: "x".to_string(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "line 17, col 9: expected number, got 'x'"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::Error;
+use crate::input;
+
+/// Coordinates of a grid cell, counted from the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Point {
+        Point { row, col }
+    }
+
+    /// The point `rows` down and `cols` to the right, if that isn't left of
+    /// or above the grid.
+    pub fn offset(&self, rows: isize, cols: isize) -> Option<Point> {
+        Some(Point {
+            row: self.row.checked_add_signed(rows)?,
+            col: self.col.checked_add_signed(cols)?,
+        })
+    }
+}
+
+/// Offsets of the cells sharing a side, in reading order.
+const SIDES: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the cells sharing a side or a corner, in reading order.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses every non-empty line of `data` as a row, turning each char into
+    /// a cell with `cell`. Chars it returns `None` for are reported as
+    /// unexpected, and so are rows shorter or longer than the first one.
+    pub fn parse<F>(data: &str, mut cell: F) -> Result<Grid<T>, Error>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in input::lines(data) {
+            let mut columns = 0;
+            for (offset, char) in line.text.char_indices() {
+                let token = &line.text[offset..offset + char.len_utf8()];
+                cells.push(cell(char).ok_or_else(|| line.unexpected(token, "grid cell"))?);
+                columns += 1;
+            }
+
+            // every row needs the same width, the neighbour lookups rely on it
+            let width = *width.get_or_insert(columns);
+            if columns != width {
+                return Err(Error::UnexpectedToken {
+                    position: line.position_after(line.text),
+                    expected: format!("{} columns", width),
+                    found: format!("{} columns", columns),
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.row * self.width + point.col])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.row * self.width + point.col])
+    }
+
+    /// Every point of the grid in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height).flat_map(|row| (0..self.width).map(move |col| Point::new(row, col)))
+    }
+
+    /// Every cell along with its point, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which can only be an empty grid
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `col` from top to bottom, which is empty for a
+    /// column outside the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let rows = if col < self.width { self.height } else { 0 };
+        (0..rows).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The up to 4 points sharing a side with `point`.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.around(point, &SIDES)
+    }
+
+    /// The up to 8 points sharing a side or a corner with `point`.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.around(point, &SURROUNDING)
+    }
+
+    fn around<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(rows, cols)| point.offset(rows, cols))
+            .filter(|&point| self.contains(point))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DATA: &str = "123
+456
+
+789
+";
+
+    fn digits(data: &str) -> Grid<u32> {
+        Grid::parse(data, |char| char.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits(TEST_DATA);
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Point::new(2, 0)], 7);
+
+        let grid = digits("");
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+        assert_eq!(grid.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let result = Grid::parse("123\n4x6", |char| char.to_digit(10));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 2: expected grid cell, got 'x'"
+        );
+
+        let result = Grid::parse("123\n45", |char| char.to_digit(10));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 3: expected 3 columns, got '2 columns'"
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = digits(TEST_DATA);
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&6));
+        assert_eq!(grid.get(Point::new(1, 3)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 0;
+        grid[Point::new(2, 2)] = 10;
+        let cells = grid.iter().map(|(_, cell)| *cell).collect::<Vec<_>>();
+        assert_eq!(cells, [0, 2, 3, 4, 5, 6, 7, 8, 10]);
+        assert_eq!(grid.points().last(), Some(Point::new(2, 2)));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits(TEST_DATA);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [[1, 2, 3], [4, 5, 6], [7, 8, 9]]
+        );
+
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5, 8]);
+        assert_eq!(grid.column(3).count(), 0);
+        let sums = grid
+            .columns()
+            .map(|column| column.sum())
+            .collect::<Vec<u32>>();
+        assert_eq!(sums, [12, 15, 18]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits(TEST_DATA);
+        let values = |points: Vec<Point>| {
+            points
+                .into_iter()
+                .map(|point| grid[point])
+                .collect::<Vec<_>>()
+        };
+
+        let middle = Point::new(1, 1);
+        assert_eq!(values(grid.neighbours4(middle).collect()), [2, 4, 6, 8]);
+        assert_eq!(
+            values(grid.neighbours8(middle).collect()),
+            [1, 2, 3, 4, 6, 7, 8, 9]
+        );
+
+        let corner = Point::new(0, 2);
+        assert_eq!(values(grid.neighbours4(corner).collect()), [2, 6]);
+        assert_eq!(values(grid.neighbours8(corner).collect()), [2, 5, 6]);
+
+        let edge = Point::new(2, 1);
+        assert_eq!(values(grid.neighbours8(edge).collect()), [4, 5, 6, 7, 9]);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = digits(TEST_DATA).map(|digit| digit % 2 == 0);
+        assert_eq!(grid.row(0), Some(&[false, true, false][..]));
+        assert_eq!(grid.width(), 3);
+    }
+}
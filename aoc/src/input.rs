@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Error, Position};
+
+/// Reads the whole puzzle input at `path`.
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<String, Error> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// A single line of the input, remembering where it came from so parse
+/// errors can point at the offending token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// 1-based line number in the input.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Line<'a> {
+        Line { number, text }
+    }
+
+    /// Position of `part`, which has to be a slice of this line's text.
+    /// Anything else points at the start of the line.
+    pub fn position(&self, part: &str) -> Position {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        let column = if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        };
+
+        Position {
+            line: self.number,
+            column,
+        }
+    }
+
+    /// Position right behind `part`.
+    pub fn position_after(&self, part: &str) -> Position {
+        let mut position = self.position(part);
+        position.column += part.chars().count();
+        position
+    }
+
+    /// Parses `token`, a slice of this line, as a number.
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, Error> {
+        token.parse::<T>().map_err(|_| Error::InvalidNumber {
+            position: self.position(token),
+            found: token.to_string(),
+        })
+    }
+
+    /// Parses every whitespace separated number in `part`, a slice of this line.
+    pub fn numbers<T: FromStr>(&self, part: &str) -> Result<Vec<T>, Error> {
+        part.split_whitespace()
+            .map(|token| self.number(token))
+            .collect()
+    }
+
+    /// Splits `part`, a slice of this line, around the first `separator`.
+    pub fn split_once(&self, part: &'a str, separator: char) -> Result<(&'a str, &'a str), Error> {
+        part.split_once(separator)
+            .ok_or_else(|| self.missing(part, format!("'{}'", separator)))
+    }
+
+    /// Checks that `part`, a slice of this line, holds `expected` once trimmed.
+    pub fn expect(&self, part: &str, expected: &str) -> Result<(), Error> {
+        let token = part.trim();
+        if token == expected {
+            Ok(())
+        } else {
+            Err(self.unexpected(token, format!("'{}'", expected)))
+        }
+    }
+
+    /// Error for an unexpected `token`, a slice of this line.
+    pub fn unexpected<S: Into<String>>(&self, token: &str, expected: S) -> Error {
+        Error::UnexpectedToken {
+            position: self.position(token),
+            expected: expected.into(),
+            found: token.to_string(),
+        }
+    }
+
+    /// Error for a `section` that should have followed `part`, a slice of this line.
+    pub fn missing<S: Into<String>>(&self, part: &str, section: S) -> Error {
+        Error::MissingSection {
+            position: self.position_after(part),
+            section: section.into(),
+        }
+    }
+}
+
+/// Position right behind the last line of `data`, used to report sections
+/// missing at the end of the input.
+pub fn end(data: &str) -> Position {
+    Position {
+        line: data.lines().count() + 1,
+        column: 1,
+    }
+}
+
+/// Iterates over the non-empty lines of `data`, keeping their line numbers.
+pub fn lines(data: &str) -> impl Iterator<Item = Line<'_>> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(index, text)| Line::new(index + 1, text))
+}
+
+/// Splits `data` into blocks of lines separated by one or more blank lines.
+pub fn blocks(data: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks: Vec<Vec<Line>> = vec![];
+    let mut current: Vec<Line> = vec![];
+
+    for (index, text) in data.lines().enumerate() {
+        if text.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(current);
+                current = vec![];
+            }
+            continue;
+        }
+        current.push(Line::new(index + 1, text));
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let result = lines("a\n\nb\r\nc\n").collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            vec![Line::new(1, "a"), Line::new(3, "b"), Line::new(4, "c")]
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let result = blocks("1\n2\n\n\n3\n\n4\n5\n");
+        let result = result
+            .iter()
+            .map(|block| block.iter().map(|line| line.number).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec![vec![1, 2], vec![5], vec![7, 8]]);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let line = Line::new(1, " 41 48  6 ");
+        let result = line.numbers::<u32>(line.text).unwrap();
+        assert_eq!(result, vec![41, 48, 6]);
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        let line = Line::new(17, "Card 1: 41 x8 | 6");
+        let result = line.numbers::<u32>(&line.text[7..]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 17, col 12: expected number, got 'x8'"
+        );
+    }
+
+    #[test]
+    fn test_missing_separator() {
+        let line = Line::new(2, "Game 1 3 blue");
+        let result = line.split_once(line.text, ':');
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, col 14: missing ':'"
+        );
+    }
+
+    #[test]
+    fn test_expect() {
+        let line = Line::new(1, "Time: 7");
+        assert!(line.expect(&line.text[..4], "Time").is_ok());
+        assert_eq!(
+            line.expect(&line.text[..4], "Distance")
+                .unwrap_err()
+                .to_string(),
+            "line 1, col 1: expected 'Distance', got 'Time'"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Shared helpers for the Advent of Code solutions.
+//!
+//! Every day crate in the workspace depends on this library, so fixes to
+//! input handling and parsing only need to land once.
+
+pub mod answers;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod regions;
+pub mod registry;
+pub mod solution;
+
+pub use answers::{Answers, Verdict};
+pub use error::{Error, Position};
+pub use grid::{Grid, Point};
+pub use input::{blocks, end, lines, read_input, Line};
+pub use regions::{Bounds, Connectivity, Region, Regions};
+pub use registry::{Answer, DynSolution, Entry, Outcome, Part, Registry};
+pub use solution::{run, Solution};
@@ -0,0 +1,276 @@
+use crate::grid::{Grid, Point};
+
+/// Which neighbours of a cell can join its region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Only the cells left and right of it, so regions never span rows.
+    Horizontal,
+    /// The cells sharing a side.
+    Four,
+    /// The cells sharing a side or a corner.
+    Eight,
+}
+
+/// The smallest rectangle holding every cell of a region, corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub top_left: Point,
+    pub bottom_right: Point,
+}
+
+impl Bounds {
+    pub fn contains(&self, point: Point) -> bool {
+        (self.top_left.row..=self.bottom_right.row).contains(&point.row)
+            && (self.top_left.col..=self.bottom_right.col).contains(&point.col)
+    }
+}
+
+/// A set of connected cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Index of the region, regions are numbered in the reading order of
+    /// their first cell.
+    pub id: usize,
+    /// The cells in reading order.
+    pub cells: Vec<Point>,
+    pub bounds: Bounds,
+}
+
+/// A grid's cells grouped into connected regions.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    /// The region of every cell, if it belongs to one.
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Groups every cell of `grid` into regions, where a cell joins the
+    /// region of a `connectivity` neighbour if `joined(cell, neighbour)`.
+    pub fn label<T, F>(grid: &Grid<T>, connectivity: Connectivity, joined: F) -> Regions
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        Regions::build(grid, connectivity, |_| true, joined)
+    }
+
+    /// Groups the cells of `grid` matching `predicate` into regions of
+    /// `connectivity` neighbours that match it as well. Other cells are
+    /// left out.
+    pub fn matching<T, F>(grid: &Grid<T>, connectivity: Connectivity, mut predicate: F) -> Regions
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Regions::build(grid, connectivity, &mut predicate, |_, _| true)
+    }
+
+    fn build<T, P, F>(
+        grid: &Grid<T>,
+        connectivity: Connectivity,
+        mut include: P,
+        mut joined: F,
+    ) -> Regions
+    where
+        P: FnMut(&T) -> bool,
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut labels = grid.map(|_| None);
+        let mut regions = vec![];
+
+        for start in grid.points() {
+            if labels[start].is_some() || !include(&grid[start]) {
+                continue;
+            }
+
+            let id = regions.len();
+            labels[start] = Some(id);
+            let mut cells = vec![];
+            let mut stack = vec![start];
+            while let Some(point) = stack.pop() {
+                cells.push(point);
+                for neighbour in neighbours(grid, point, connectivity) {
+                    if labels[neighbour].is_none()
+                        && include(&grid[neighbour])
+                        && joined(&grid[point], &grid[neighbour])
+                    {
+                        labels[neighbour] = Some(id);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            cells.sort();
+
+            let bounds = Bounds {
+                top_left: Point::new(
+                    cells[0].row,
+                    cells.iter().map(|cell| cell.col).min().unwrap(),
+                ),
+                bottom_right: Point::new(
+                    cells[cells.len() - 1].row,
+                    cells.iter().map(|cell| cell.col).max().unwrap(),
+                ),
+            };
+            regions.push(Region { id, cells, bounds });
+        }
+
+        Regions { labels, regions }
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Region> {
+        self.regions.get(id)
+    }
+
+    /// The region `point` belongs to.
+    pub fn at(&self, point: Point) -> Option<&Region> {
+        let id = (*self.labels.get(point)?)?;
+        self.regions.get(id)
+    }
+
+    /// The regions with a cell sharing a side or a corner with `point`,
+    /// ordered by id. The region of `point` itself isn't included.
+    pub fn touching(&self, point: Point) -> Vec<&Region> {
+        self.around(&[point])
+    }
+
+    /// The other regions with a cell sharing a side or a corner with one of
+    /// `region`'s cells, ordered by id.
+    pub fn neighbours(&self, region: &Region) -> Vec<&Region> {
+        self.around(&region.cells)
+    }
+
+    fn around(&self, cells: &[Point]) -> Vec<&Region> {
+        let own = cells
+            .iter()
+            .filter_map(|&cell| self.labels.get(cell).copied().flatten())
+            .collect::<Vec<_>>();
+
+        let mut ids = cells
+            .iter()
+            .flat_map(|&cell| self.labels.neighbours8(cell))
+            .filter_map(|point| self.labels[point])
+            .filter(|id| !own.contains(id))
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+
+        ids.into_iter().map(|id| &self.regions[id]).collect()
+    }
+}
+
+fn neighbours<T>(grid: &Grid<T>, point: Point, connectivity: Connectivity) -> Vec<Point> {
+    match connectivity {
+        Connectivity::Horizontal => [point.offset(0, -1), point.offset(0, 1)]
+            .into_iter()
+            .flatten()
+            .filter(|&point| grid.contains(point))
+            .collect(),
+        Connectivity::Four => grid.neighbours4(point).collect(),
+        Connectivity::Eight => grid.neighbours8(point).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_DATA: &str = "12.4
+.3..
+..56";
+
+    fn digits(connectivity: Connectivity) -> Regions {
+        let grid = Grid::parse(TEST_DATA, Some).unwrap();
+        Regions::matching(&grid, connectivity, |cell| cell.is_ascii_digit())
+    }
+
+    fn cells(regions: &Regions) -> Vec<Vec<(usize, usize)>> {
+        regions
+            .iter()
+            .map(|region| {
+                region
+                    .cells
+                    .iter()
+                    .map(|cell| (cell.row, cell.col))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_connectivity() {
+        let regions = digits(Connectivity::Horizontal);
+        assert_eq!(
+            cells(&regions),
+            [
+                vec![(0, 0), (0, 1)],
+                vec![(0, 3)],
+                vec![(1, 1)],
+                vec![(2, 2), (2, 3)]
+            ]
+        );
+
+        let regions = digits(Connectivity::Four);
+        assert_eq!(
+            cells(&regions),
+            [
+                vec![(0, 0), (0, 1), (1, 1)],
+                vec![(0, 3)],
+                vec![(2, 2), (2, 3)]
+            ]
+        );
+
+        let regions = digits(Connectivity::Eight);
+        assert_eq!(
+            cells(&regions),
+            [vec![(0, 0), (0, 1), (1, 1), (2, 2), (2, 3)], vec![(0, 3)]]
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let regions = digits(Connectivity::Eight);
+        let bounds = regions.get(0).unwrap().bounds;
+        assert_eq!(bounds.top_left, Point::new(0, 0));
+        assert_eq!(bounds.bottom_right, Point::new(2, 3));
+        assert!(bounds.contains(Point::new(1, 2)));
+        assert!(!bounds.contains(Point::new(3, 0)));
+    }
+
+    #[test]
+    fn test_label() {
+        let grid = Grid::parse("aab\nabb", Some).unwrap();
+        let regions = Regions::label(&grid, Connectivity::Four, |a, b| a == b);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions.at(Point::new(1, 0)).unwrap().id, 0);
+        assert_eq!(regions.at(Point::new(1, 1)).unwrap().id, 1);
+        assert_eq!(regions.at(Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_touching() {
+        let regions = digits(Connectivity::Horizontal);
+        let ids =
+            |regions: Vec<&Region>| regions.iter().map(|region| region.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(regions.touching(Point::new(1, 2))), [0, 1, 2, 3]);
+        assert_eq!(ids(regions.touching(Point::new(0, 2))), [0, 1, 2]);
+        // a cell doesn't touch its own region
+        assert_eq!(ids(regions.touching(Point::new(0, 0))), [2]);
+        assert_eq!(ids(regions.touching(Point::new(2, 0))), [2]);
+
+        let region = regions.at(Point::new(2, 3)).unwrap();
+        assert_eq!(ids(regions.neighbours(region)), [2]);
+        assert!(regions.at(Point::new(2, 0)).is_none());
+    }
+}
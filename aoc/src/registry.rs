@@ -0,0 +1,203 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::solution::Solution;
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part one"),
+            Part::Two => write!(f, "Part two"),
+        }
+    }
+}
+
+/// The answer of a single part and how long it took to compute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// Everything a single run of a day produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Wall time of the parse step.
+    pub parse: Duration,
+    /// The answers of the selected parts, in order.
+    pub answers: Vec<Answer>,
+}
+
+/// Object safe view of a [`Solution`], so days with different input and
+/// answer types can live in the same [`Registry`].
+pub trait DynSolution {
+    /// Parses `data` and runs the selected `parts`, timing every step.
+    fn solve(&self, data: &str, parts: &[Part]) -> Result<Outcome, Error>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn solve(&self, data: &str, parts: &[Part]) -> Result<Outcome, Error> {
+        let start = Instant::now();
+        let input = self.parse(data)?;
+        let parse = start.elapsed();
+
+        let answers = parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                let value = match part {
+                    Part::One => self.part_one(&input)?,
+                    Part::Two => self.part_two(&input)?,
+                };
+                let elapsed = start.elapsed();
+
+                Ok(Answer {
+                    part: *part,
+                    value: value.to_string(),
+                    elapsed,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Outcome { parse, answers })
+    }
+}
+
+/// A registered day: where it lives and how to solve it.
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    /// Directory of the day crate, holding its `data.txt` and `answers.txt`.
+    pub dir: PathBuf,
+    pub solution: Box<dyn DynSolution>,
+}
+
+impl Entry {
+    /// The default puzzle input of this day.
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("data.txt")
+    }
+
+    /// The accepted answers for the default puzzle input.
+    pub fn answers(&self) -> PathBuf {
+        self.dir.join("answers.txt")
+    }
+}
+
+/// Every known solution, looked up by year and day.
+#[derive(Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn register<S, P>(&mut self, year: u32, day: u32, dir: P, solution: S)
+    where
+        S: Solution + 'static,
+        P: AsRef<Path>,
+    {
+        self.entries.push(Entry {
+            year,
+            day,
+            dir: dir.as_ref().to_path_buf(),
+            solution: Box::new(solution),
+        });
+        self.entries.sort_by_key(|entry| (entry.year, entry.day));
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.year == year && entry.day == day)
+    }
+
+    /// All days of `year`, in day order.
+    pub fn year(&self, year: u32) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |entry| entry.year == year)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Position;
+    use crate::input::Line;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(&self, data: &str) -> Result<Vec<u32>, Error> {
+            let line = Line::new(1, data);
+            line.numbers(data)
+        }
+
+        fn part_one(&self, input: &Vec<u32>) -> Result<u32, Error> {
+            Ok(input.iter().sum())
+        }
+
+        fn part_two(&self, input: &Vec<u32>) -> Result<u32, Error> {
+            input
+                .iter()
+                .try_fold(1u32, |product, &n| product.checked_mul(n))
+                .ok_or_else(|| Error::Overflow {
+                    position: Position { line: 1, column: 1 },
+                    quantity: "the product".to_string(),
+                })
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register(2023, 2, "day_2", Sum);
+        registry.register(2023, 1, "day_1", Sum);
+        registry.register(2022, 1, "day_1", Sum);
+
+        let days = registry.year(2023).map(|e| e.day).collect::<Vec<_>>();
+        assert_eq!(days, vec![1, 2]);
+        assert!(registry.get(2021, 1).is_none());
+
+        let entry = registry.get(2023, 2).unwrap();
+        assert_eq!(entry.input(), PathBuf::from("day_2/data.txt"));
+
+        let outcome = entry.solution.solve("2 3 4", &[Part::Two]).unwrap();
+        let answers = outcome
+            .answers
+            .iter()
+            .map(|answer| (answer.part, answer.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(answers, vec![(Part::Two, "24")]);
+
+        // a failing part fails the whole run
+        let result = entry.solution.solve("65536 65536", &[Part::One, Part::Two]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 1, col 1: the product is too large"
+        );
+    }
+}
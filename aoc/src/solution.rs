@@ -0,0 +1,41 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::process;
+
+use crate::error::Error;
+use crate::input::read_input;
+
+/// A single day's puzzle: a parse step followed by two parts that share the
+/// parsed input. All three fail with an error rather than panicking, the
+/// parts for answers that can't be counted.
+pub trait Solution {
+    /// The parsed puzzle input handed to both parts.
+    type Input;
+    /// The answer produced by both parts.
+    type Answer: Display;
+
+    fn parse(&self, data: &str) -> Result<Self::Input, Error>;
+
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, Error>;
+
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, Error>;
+}
+
+/// Parses the input at `path` and prints the answer of both parts, exiting
+/// with an error message when the input can't be read or parsed, or a part
+/// fails.
+pub fn run<S: Solution, P: AsRef<Path>>(solution: &S, path: P) {
+    let answers = read_input(path)
+        .and_then(|data| solution.parse(&data))
+        .and_then(|input| Ok((solution.part_one(&input)?, solution.part_two(&input)?)));
+    match answers {
+        Ok((one, two)) => {
+            println!("Part one: {}", one);
+            println!("Part two: {}", two);
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+use aoc::Part;
+
+pub const USAGE: &str = "Usage:
+    aoc run <year> [day] [--part <1|2>] [--input <path>] [--record]
+    aoc bench <year> [day] [--part <1|2>] [--runs <n>]
+    aoc list";
+
+/// How often `bench` runs every day unless told otherwise.
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Runs one day, or every day of a year when `day` is missing.
+    Run {
+        year: u32,
+        day: Option<u32>,
+        parts: Vec<Part>,
+        input: Option<PathBuf>,
+        /// Store the answers as the accepted ones instead of checking them.
+        record: bool,
+    },
+    /// Runs one day, or every day of a year, `runs` times and reports
+    /// min/median/max timings.
+    Bench {
+        year: u32,
+        day: Option<u32>,
+        parts: Vec<Part>,
+        runs: usize,
+    },
+    /// Lists every registered day.
+    List,
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("bench") => parse_bench(args),
+        Some("list") => Ok(Command::List),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+/// Everything `run` and `bench` accept; each command rejects what it
+/// doesn't use.
+struct Options {
+    year: u32,
+    day: Option<u32>,
+    parts: Vec<Part>,
+    input: Option<PathBuf>,
+    record: bool,
+    runs: Option<usize>,
+}
+
+fn parse_options<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut positional: Vec<u32> = vec![];
+    let mut parts = Part::ALL.to_vec();
+    let mut input = None;
+    let mut record = false;
+    let mut runs = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                parts = match args.next().as_deref() {
+                    Some("1") => vec![Part::One],
+                    Some("2") => vec![Part::Two],
+                    Some(other) => return Err(format!("invalid part '{}'", other)),
+                    None => return Err("missing value for --part".to_string()),
+                };
+            }
+            "--input" | "-i" => match args.next() {
+                Some(path) => input = Some(PathBuf::from(path)),
+                None => return Err("missing value for --input".to_string()),
+            },
+            "--record" => record = true,
+            "--runs" | "-n" => match args.next() {
+                Some(value) => match parse_number(&value)? {
+                    0 => return Err("--runs needs at least one run".to_string()),
+                    value => runs = Some(value as usize),
+                },
+                None => return Err("missing value for --runs".to_string()),
+            },
+            _ => positional.push(parse_number(&arg)?),
+        }
+    }
+
+    let (year, day) = match positional[..] {
+        [year] => (year, None),
+        [year, day] => (year, Some(day)),
+        [] => return Err("missing year".to_string()),
+        _ => return Err("too many arguments".to_string()),
+    };
+
+    Ok(Options {
+        year,
+        day,
+        parts,
+        input,
+        record,
+        runs,
+    })
+}
+
+fn parse_run<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let options = parse_options(args)?;
+
+    if options.runs.is_some() {
+        return Err("--runs only works with bench".to_string());
+    }
+    if options.input.is_some() && options.day.is_none() {
+        return Err("--input needs a day".to_string());
+    }
+    if options.input.is_some() && options.record {
+        return Err("--record only works with the default input".to_string());
+    }
+
+    Ok(Command::Run {
+        year: options.year,
+        day: options.day,
+        parts: options.parts,
+        input: options.input,
+        record: options.record,
+    })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let options = parse_options(args)?;
+
+    if options.input.is_some() || options.record {
+        return Err("bench only works with the default input".to_string());
+    }
+
+    Ok(Command::Bench {
+        year: options.year,
+        day: options.day,
+        parts: options.parts,
+        runs: options.runs.unwrap_or(DEFAULT_RUNS),
+    })
+}
+
+fn parse_number(arg: &str) -> Result<u32, String> {
+    arg.parse::<u32>()
+        .map_err(|_| format!("expected a number, got '{}'", arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(data: &str) -> Vec<String> {
+        data.split_whitespace().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_run_day() {
+        let command = parse(args("run 2023 5 --part 2 --input other.txt")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Run {
+                year: 2023,
+                day: Some(5),
+                parts: vec![Part::Two],
+                input: Some(PathBuf::from("other.txt")),
+                record: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_run_year() {
+        let command = parse(args("run 2023")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Run {
+                year: 2023,
+                day: None,
+                parts: vec![Part::One, Part::Two],
+                input: None,
+                record: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let command = parse(args("run 2023 --part 1 --record")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Run {
+                year: 2023,
+                day: None,
+                parts: vec![Part::One],
+                input: None,
+                record: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_bench() {
+        let command = parse(args("bench 2023 --runs 5")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Bench {
+                year: 2023,
+                day: None,
+                parts: vec![Part::One, Part::Two],
+                runs: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse(args("")).is_err());
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run 2023 five")).is_err());
+        assert!(parse(args("run 2023 5 --part 3")).is_err());
+        assert!(parse(args("run 2023 --input data.txt")).is_err());
+        assert!(parse(args("run 2023 5 --input data.txt --record")).is_err());
+        assert!(parse(args("run 2023 5 --runs 3")).is_err());
+        assert!(parse(args("bench 2023 --runs 0")).is_err());
+        assert!(parse(args("bench 2023 5 --record")).is_err());
+    }
+}
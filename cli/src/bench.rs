@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use aoc::{Entry, Error, Part};
+
+/// Spread of the timings of one step over several runs.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which must not be empty.
+    pub fn new(samples: &[Duration]) -> Stats {
+        let mut samples = samples.to_vec();
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Renders `duration` with a unit fitting its size, e.g. `1.25ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Runs every entry `runs` times on its default input and prints a table
+/// with the min/median/max wall time of the parse step and each part.
+pub fn bench(entries: &[&Entry], parts: &[Part], runs: usize) -> bool {
+    println!(
+        "{:<6} {:>4}  {:<8} {:>10} {:>10} {:>10}",
+        "year", "day", "step", "min", "median", "max"
+    );
+
+    let mut passed = true;
+    for entry in entries {
+        match measure(entry, parts, runs) {
+            Ok(rows) => {
+                for (step, stats) in rows {
+                    println!(
+                        "{:<6} {:>4}  {:<8} {:>10} {:>10} {:>10}",
+                        entry.year,
+                        entry.day,
+                        step,
+                        format_duration(stats.min),
+                        format_duration(stats.median),
+                        format_duration(stats.max)
+                    );
+                }
+            }
+            Err(err) => {
+                eprintln!("{:<6} {:>4}  error: {}", entry.year, entry.day, err);
+                passed = false;
+            }
+        }
+    }
+    passed
+}
+
+fn measure(
+    entry: &Entry,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Vec<(&'static str, Stats)>, Error> {
+    let data = aoc::read_input(entry.input())?;
+
+    let mut parse = vec![];
+    let mut answers: Vec<Vec<Duration>> = vec![vec![]; parts.len()];
+    for _ in 0..runs {
+        let outcome = entry.solution.solve(&data, parts)?;
+        parse.push(outcome.parse);
+        for (samples, answer) in answers.iter_mut().zip(outcome.answers) {
+            samples.push(answer.elapsed);
+        }
+    }
+
+    let mut rows = vec![("parse", Stats::new(&parse))];
+    for (part, samples) in parts.iter().zip(answers) {
+        let step = match part {
+            Part::One => "part one",
+            Part::Two => "part two",
+        };
+        rows.push((step, Stats::new(&samples)));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_millis(*x)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&millis(&[5, 1, 3]));
+        assert_eq!(
+            stats,
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+
+        let stats = Stats::new(&millis(&[4, 1, 2, 8]));
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_250)), "1.25µs");
+        assert_eq!(format_duration(Duration::from_micros(2_500)), "2.50ms");
+        assert_eq!(format_duration(Duration::from_millis(3_000)), "3.00s");
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use aoc::Registry;
+
+/// Directory of a day crate, relative to the workspace root.
+fn day_dir(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(path)
+}
+
+/// Every solution in the workspace. New days are added here.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(2022, 1, day_dir("2022/rust/day_1"), year2022_day_1::Day1);
+
+    registry.register(2023, 1, day_dir("2023/rust/day_1"), year2023_day_1::Day1);
+    registry.register(
+        2023,
+        2,
+        day_dir("2023/rust/day_2"),
+        year2023_day_2::Day2::default(),
+    );
+    registry.register(
+        2023,
+        3,
+        day_dir("2023/rust/day_3"),
+        year2023_day_3::Day3::default(),
+    );
+    registry.register(
+        2023,
+        4,
+        day_dir("2023/rust/day_4"),
+        year2023_day_4::Day4::default(),
+    );
+    registry.register(2023, 5, day_dir("2023/rust/day_5"), year2023_day_5::Day5);
+    registry.register(2023, 6, day_dir("2023/rust/day_6"), year2023_day_6::Day6);
+
+    registry
+}
@@ -0,0 +1,155 @@
+mod args;
+mod bench;
+mod days;
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc::{Answers, Entry, Error, Part, Registry, Verdict};
+use args::Command;
+use bench::format_duration;
+
+fn main() -> ExitCode {
+    let command = match args::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let registry = days::registry();
+
+    match command {
+        Command::List => {
+            for entry in registry.iter() {
+                println!("{} day {}", entry.year, entry.day);
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Run {
+            year,
+            day,
+            parts,
+            input,
+            record,
+        } => {
+            let entries = select(&registry, year, day);
+            if entries.is_empty() {
+                eprintln!("error: no solution registered for {}", describe(year, day));
+                return ExitCode::FAILURE;
+            }
+
+            let mut failed = false;
+            for entry in entries {
+                if !run(entry, &parts, input.clone(), record) {
+                    failed = true;
+                }
+            }
+
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Bench {
+            year,
+            day,
+            parts,
+            runs,
+        } => {
+            let entries = select(&registry, year, day);
+            if entries.is_empty() {
+                eprintln!("error: no solution registered for {}", describe(year, day));
+                return ExitCode::FAILURE;
+            }
+
+            if bench::bench(&entries, &parts, runs) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+fn select(registry: &Registry, year: u32, day: Option<u32>) -> Vec<&Entry> {
+    match day {
+        Some(day) => registry.get(year, day).into_iter().collect(),
+        None => registry.year(year).collect(),
+    }
+}
+
+fn describe(year: u32, day: Option<u32>) -> String {
+    match day {
+        Some(day) => format!("{} day {}", year, day),
+        None => year.to_string(),
+    }
+}
+
+/// Runs the selected parts of one day and prints the answers with their
+/// timings. Answers for the default input are checked against, or recorded
+/// to, the day's `answers.txt`. Returns false when the day errored or an
+/// answer is wrong.
+fn run(entry: &Entry, parts: &[Part], input: Option<PathBuf>, record: bool) -> bool {
+    match solve(entry, parts, input, record) {
+        Ok(passed) => passed,
+        Err(err) => {
+            eprintln!("{} day {}  error: {}", entry.year, entry.day, err);
+            false
+        }
+    }
+}
+
+fn solve(
+    entry: &Entry,
+    parts: &[Part],
+    input: Option<PathBuf>,
+    record: bool,
+) -> Result<bool, Error> {
+    // recorded answers only belong to the default input
+    let mut answers = match input {
+        Some(_) => None,
+        None => Some(Answers::load(entry.answers())?),
+    };
+
+    let data = aoc::read_input(input.unwrap_or_else(|| entry.input()))?;
+    let outcome = entry.solution.solve(&data, parts)?;
+
+    println!(
+        "{} day {}  (parse {})",
+        entry.year,
+        entry.day,
+        format_duration(outcome.parse)
+    );
+
+    let mut passed = true;
+    for answer in outcome.answers {
+        let (part, value) = (answer.part, answer.value);
+        let elapsed = format_duration(answer.elapsed);
+        match answers.as_mut() {
+            Some(answers) if record => {
+                answers.set(part, value.as_str());
+                println!("  {}: {}  RECORDED  ({})", part, value, elapsed);
+            }
+            Some(answers) => {
+                let verdict = answers.check(part, &value);
+                if let Verdict::Fail { .. } = verdict {
+                    passed = false;
+                }
+                println!("  {}: {}  {}  ({})", part, value, verdict, elapsed);
+            }
+            None => println!("  {}: {}  ({})", part, value, elapsed),
+        }
+    }
+
+    if record {
+        if let Some(answers) = answers {
+            answers.save(entry.answers())?;
+        }
+    }
+
+    Ok(passed)
+}